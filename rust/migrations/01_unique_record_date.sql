@@ -0,0 +1,12 @@
+-- Only one record per day, keep the most recent one when a day was logged twice.
+-- The older records are kept in RecordDuplicate instead of being lost.
+CREATE TABLE IF NOT EXISTS RecordDuplicate AS
+SELECT * FROM Record
+WHERE id NOT IN (
+    SELECT MAX(id) FROM Record GROUP BY date
+);
+
+DELETE FROM Record
+WHERE id IN (SELECT id FROM RecordDuplicate);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_record_date ON Record(date);
@@ -0,0 +1,2 @@
+-- Archived locations are hidden from pickers but keep their records for the stats
+ALTER TABLE Location ADD COLUMN archived BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,2 @@
+-- What kind of place a location is, 0 is uncategorized
+ALTER TABLE Location ADD COLUMN category INTEGER NOT NULL DEFAULT 0;
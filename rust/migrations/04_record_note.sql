@@ -0,0 +1,2 @@
+-- Optional free text about a day, e.g. why it was worked from home
+ALTER TABLE Record ADD COLUMN note TEXT;
@@ -0,0 +1,405 @@
+use clap::{ArgGroup, Args, Parser, Subcommand};
+use color_eyre::{Result, eyre::eyre};
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::PathBuf,
+};
+use time::Date;
+
+use crate::{
+    config::config,
+    dates::{parse_date, today},
+    export::{ExportFormat, write_locations, write_records},
+    holidays::{Country, Holiday, holidays},
+    import::{ImportPlan, python_data_dir, read_python_locations, read_python_records},
+    stats::is_workday,
+    store::{Category, Location, LogType, NamedRecord, Profile, Store},
+};
+
+/// Locwork tracks the location you work from each day.
+///
+/// Run without a command to start the interactive calendar.
+#[derive(Parser)]
+#[command(name = "locwork", version)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    /// Highlight the public holidays of this country in the calendar
+    #[arg(long, value_enum)]
+    pub country: Option<Country>,
+    /// Database file to use, overrides the profile
+    #[arg(long, global = true)]
+    pub db: Option<PathBuf>,
+    /// Named profile with its own database, e.g. one per employer
+    #[arg(long, global = true)]
+    pub profile: Option<String>,
+    /// Config file to use instead of config.toml in the config folder
+    #[arg(long, global = true)]
+    pub config: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Manage the known locations
+    #[command(subcommand)]
+    Location(LocationCommand),
+    /// Manage the logged days
+    #[command(subcommand)]
+    Log(LogCommand),
+    /// Import the records.csv and locations files of the python version of locwork
+    Import(ImportArgs),
+    /// Export records or locations
+    #[command(subcommand)]
+    Export(ExportCommand),
+    /// Public holidays of the built-in country calendars
+    #[command(subcommand)]
+    Holidays(HolidaysCommand),
+    /// List the profiles that have a database
+    Profiles,
+}
+
+#[derive(Subcommand)]
+pub enum LocationCommand {
+    /// Add a new location
+    Add {
+        name: String,
+        #[arg(long)]
+        tag: Option<String>,
+        /// home, office, client or travel
+        #[arg(long, value_parser = parse_category_arg)]
+        category: Option<Category>,
+    },
+    /// Set the category of a location: home, office, client or travel
+    Category {
+        name: String,
+        #[arg(value_parser = parse_category_arg)]
+        category: Category,
+    },
+    /// Remove a location and every record logged there
+    Remove { name: String },
+    /// Archive a location, hiding it from the pickers while keeping its records
+    Archive { name: String },
+    /// Make an archived location available again
+    Unarchive { name: String },
+    /// List all locations
+    List,
+}
+
+#[derive(Subcommand)]
+pub enum LogCommand {
+    /// Add a record for a date, overwriting the record already logged that day
+    Add(LogAddArgs),
+}
+
+#[derive(Args)]
+#[command(group(ArgGroup::new("day").required(true).args(["today", "date"])))]
+pub struct LogAddArgs {
+    /// Name of a known location
+    location: String,
+    /// Add a record for today
+    #[arg(short, long)]
+    today: bool,
+    /// Add a record for a specific date (YYYY-MM-DD)
+    #[arg(short, long, value_parser = parse_date_arg)]
+    date: Option<Date>,
+    /// The kind of day to log, defaults to `default_log_type` of the config
+    #[arg(long = "type", value_parser = parse_log_type_arg, conflicts_with = "holiday")]
+    log_type: Option<LogType>,
+    /// Log the day as a holiday, same as `--type holiday`
+    #[arg(long)]
+    holiday: bool,
+    /// Free text about the day, e.g. "client visit"
+    #[arg(long)]
+    note: Option<String>,
+}
+
+#[derive(Subcommand)]
+pub enum ExportCommand {
+    /// Export records together with the name and tag of their location, or as calendar events
+    Records(ExportRecordsArgs),
+    /// Export all locations
+    Locations(ExportOutputArgs),
+}
+
+#[derive(Args)]
+pub struct ExportOutputArgs {
+    #[arg(short, long, value_enum, default_value = "csv")]
+    format: ExportFormat,
+    /// File to write to, defaults to stdout
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+pub struct ExportRecordsArgs {
+    #[command(flatten)]
+    output: ExportOutputArgs,
+    /// Only export records on or after this date (YYYY-MM-DD)
+    #[arg(long, value_parser = parse_date_arg)]
+    from: Option<Date>,
+    /// Only export records on or before this date (YYYY-MM-DD)
+    #[arg(long, value_parser = parse_date_arg)]
+    to: Option<Date>,
+    /// Only export records of this location
+    #[arg(short, long)]
+    location: Option<String>,
+}
+
+impl ExportOutputArgs {
+    fn writer(&self) -> Result<Box<dyn Write>> {
+        match &self.output {
+            Some(path) => {
+                Ok(Box::new(File::create(path).map_err(|e| {
+                    eyre!("could not create {}: {e}", path.display())
+                })?))
+            }
+            None => Ok(Box::new(io::stdout())),
+        }
+    }
+}
+
+#[derive(Subcommand)]
+pub enum HolidaysCommand {
+    /// List the public holidays of a year
+    List(HolidaysArgs),
+    /// Log the public holidays of a year that fall on a workday as Holiday records
+    Add {
+        #[command(flatten)]
+        holidays: HolidaysArgs,
+        /// Location the holidays are logged at
+        #[arg(short, long)]
+        location: String,
+        /// Overwrite days that are already logged
+        #[arg(long)]
+        overwrite: bool,
+    },
+}
+
+#[derive(Args)]
+pub struct HolidaysArgs {
+    #[arg(short, long, value_enum)]
+    country: Country,
+    /// Defaults to the current year
+    #[arg(short, long, value_parser = clap::value_parser!(i32).range(1583..=9999))]
+    year: Option<i32>,
+}
+
+impl HolidaysArgs {
+    fn holidays(&self) -> Vec<Holiday> {
+        let year = self.year.unwrap_or(today().year());
+        holidays(self.country, year)
+    }
+}
+
+#[derive(Args)]
+pub struct ImportArgs {
+    /// Path to records.csv, defaults to the python locwork data folder
+    #[arg(long)]
+    records: Option<PathBuf>,
+    /// Path to the locations file, defaults to the python locwork data folder
+    #[arg(long)]
+    locations: Option<PathBuf>,
+    /// Only report what would be imported
+    #[arg(long)]
+    dry_run: bool,
+    /// Overwrite days that are already logged with a different location or type
+    #[arg(long)]
+    overwrite: bool,
+}
+
+fn parse_date_arg(value: &str) -> Result<Date, String> {
+    parse_date(value).ok_or(format!("'{value}' is not a date, expected YYYY-MM-DD"))
+}
+
+fn parse_log_type_arg(value: &str) -> Result<LogType, String> {
+    value.parse()
+}
+
+fn parse_category_arg(value: &str) -> Result<Category, String> {
+    value.parse()
+}
+
+/// Run a single command against the database of the profile.
+///
+/// Failures are returned as errors so the process exits with a non-zero code.
+pub async fn run(command: Command, profile: &Profile) -> Result<()> {
+    if let Command::Profiles = command {
+        for name in Profile::list() {
+            println!("{name}");
+        }
+        return Ok(());
+    }
+    let store = &Store::new(profile).await?;
+    match command {
+        Command::Location(LocationCommand::Add {
+            name,
+            tag,
+            category,
+        }) => {
+            if store.get_location_by_name(&name).await?.is_some() {
+                return Err(eyre!("location '{name}' already exists"));
+            }
+            store
+                .add_location(name.clone(), tag, category.unwrap_or(Category::Unknown))
+                .await?;
+            println!("-- added location '{name}' --");
+        }
+        Command::Location(LocationCommand::Category { name, category }) => {
+            let Some(location) = store.get_location_by_name(&name).await? else {
+                return Err(eyre!("location '{name}' is not recognized"));
+            };
+            store
+                .update_location(&Location {
+                    category,
+                    ..location
+                })
+                .await?;
+            println!("-- location '{name}' is now {category} --");
+        }
+        Command::Location(LocationCommand::Remove { name }) => {
+            if store.get_location_by_name(&name).await?.is_none() {
+                return Err(eyre!("location '{name}' is not recognized"));
+            }
+            store.delete_location_by_name(&name).await?;
+            println!("-- removed location '{name}' --");
+        }
+        Command::Location(LocationCommand::Archive { name }) => {
+            store.archive_location(&name).await?;
+            println!("-- archived location '{name}' --");
+        }
+        Command::Location(LocationCommand::Unarchive { name }) => {
+            store.unarchive_location(&name).await?;
+            println!("-- unarchived location '{name}' --");
+        }
+        Command::Location(LocationCommand::List) => {
+            for location in store.get_locations().await? {
+                let mut line = location.name.clone();
+                if location.category != Category::Unknown {
+                    line.push_str(&format!(
+                        " [{}]",
+                        location.category.to_string().to_lowercase()
+                    ));
+                }
+                if location.archived {
+                    line.push_str(" (archived)");
+                }
+                println!("{line}");
+            }
+        }
+        Command::Log(LogCommand::Add(args)) => {
+            let date = match (args.today, args.date) {
+                (false, Some(date)) => date,
+                _ => today(),
+            };
+            let log_type = if args.holiday {
+                LogType::Holiday
+            } else {
+                args.log_type.unwrap_or(config().default_log_type.clone())
+            };
+            let Some(location) = store.get_location_by_name(&args.location).await? else {
+                return Err(eyre!("location '{}' is not recognized", args.location));
+            };
+            if location.archived {
+                return Err(eyre!("location '{}' is archived", args.location));
+            }
+            let updated = store.get_record_by_date(date).await?.is_some();
+            if updated {
+                store
+                    .upsert_record(date, log_type, location, args.note)
+                    .await?;
+            } else {
+                store
+                    .add_record(date, log_type, location, args.note)
+                    .await?;
+            }
+            let verb = if updated { "updated" } else { "added" };
+            println!("-- {verb} record: {date} , {} --", args.location);
+        }
+        Command::Import(args) => {
+            let data_dir = python_data_dir().unwrap_or_default();
+            let records_path = args.records.unwrap_or(data_dir.join("records.csv"));
+            let locations_path = args.locations.unwrap_or(data_dir.join("locations"));
+
+            let locations = read_python_locations(&locations_path)?;
+            let (records, invalid_rows) = read_python_records(&records_path)?;
+            let plan =
+                ImportPlan::new(store, locations, records, invalid_rows, args.overwrite).await?;
+
+            println!("-- import from {} --", records_path.display());
+            plan.report(args.overwrite);
+            if args.dry_run {
+                println!("-- dry run, nothing was written --");
+                return Ok(());
+            }
+            store
+                .import_records(&plan.new_locations, &plan.records)
+                .await?;
+            println!("-- imported {} records --", plan.records.len());
+        }
+        Command::Export(ExportCommand::Records(args)) => {
+            if let Some(name) = &args.location
+                && store.get_location_by_name(name).await?.is_none()
+            {
+                return Err(eyre!("location '{name}' is not recognized"));
+            }
+            let records = store
+                .get_record_details(args.from, args.to, args.location.as_deref())
+                .await?;
+            write_records(
+                &records,
+                args.output.format,
+                &profile.calendar_id(),
+                args.output.writer()?,
+            )?;
+        }
+        Command::Profiles => unreachable!("profiles are listed before the store is opened"),
+        Command::Holidays(HolidaysCommand::List(args)) => {
+            for holiday in args.holidays() {
+                println!("{} {}", holiday.date, holiday.name);
+            }
+        }
+        Command::Holidays(HolidaysCommand::Add {
+            holidays,
+            location,
+            overwrite,
+        }) => {
+            let Some(found) = store.get_location_by_name(&location).await? else {
+                return Err(eyre!("location '{location}' is not recognized"));
+            };
+            if found.archived {
+                return Err(eyre!("location '{location}' is archived"));
+            }
+            let mut records = Vec::new();
+            let mut skipped = 0;
+            for holiday in holidays.holidays() {
+                if !is_workday(holiday.date) {
+                    continue;
+                }
+                if !overwrite && store.get_record_by_date(holiday.date).await?.is_some() {
+                    skipped += 1;
+                    continue;
+                }
+                records.push(NamedRecord {
+                    location: location.clone(),
+                    date: holiday.date,
+                    log_type: LogType::Holiday,
+                });
+            }
+            store.import_records(&[], &records).await?;
+            println!(
+                "-- added {} holidays, skipped {skipped} already logged days --",
+                records.len()
+            );
+        }
+        Command::Export(ExportCommand::Locations(args)) => {
+            // checked before the output file is created, so a wrong format leaves it alone
+            if args.format == ExportFormat::Ics {
+                return Err(eyre!("only records can be exported as ics"));
+            }
+            let locations = store.get_locations().await?;
+            write_locations(&locations, args.format, args.writer()?)?;
+        }
+    }
+    Ok(())
+}
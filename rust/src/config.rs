@@ -0,0 +1,256 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::OnceLock,
+};
+
+use clap::ValueEnum;
+use color_eyre::{Result, eyre::eyre};
+use ratatui::style::Color;
+use serde::Deserialize;
+use time::{
+    OffsetDateTime, UtcOffset, Weekday,
+    format_description::{self, OwnedFormatItem},
+    macros::format_description,
+};
+
+use crate::{
+    holidays::Country,
+    keymap::{KeyBindings, Keymap},
+    panels::day_colors::ColorBy,
+    store::LogType,
+};
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+static LOCAL_OFFSET: OnceLock<Option<UtcOffset>> = OnceLock::new();
+
+/// The folder locwork keeps its config file and databases in, e.g. `~/.config/locwork`
+pub fn config_dir() -> PathBuf {
+    let dirs = directories::ProjectDirs::from("be", "waystone", "locwork").unwrap();
+    dirs.config_dir().to_path_buf()
+}
+
+pub fn default_config_path() -> PathBuf {
+    config_dir().join("config.toml")
+}
+
+/// Look up the local UTC offset.
+///
+/// This only works while the process has a single thread, so it is called before the
+/// async runtime starts.
+pub fn init_local_offset() {
+    let _ = LOCAL_OFFSET.set(UtcOffset::current_local_offset().ok());
+}
+
+/// Make the config available through [`config`], can only be done once
+pub fn init(config: Config) {
+    let _ = CONFIG.set(config);
+}
+
+/// The loaded config, or the defaults when no config was loaded
+pub fn config() -> &'static Config {
+    CONFIG.get_or_init(Config::default)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Timezone {
+    Utc,
+    /// The offset of the system, falls back to UTC when it can't be determined
+    Local,
+    Offset(UtcOffset),
+}
+
+impl Timezone {
+    pub fn now(&self) -> OffsetDateTime {
+        let offset = match self {
+            Timezone::Utc => UtcOffset::UTC,
+            Timezone::Local => LOCAL_OFFSET
+                .get()
+                .copied()
+                .flatten()
+                .unwrap_or(UtcOffset::UTC),
+            Timezone::Offset(offset) => *offset,
+        };
+        OffsetDateTime::now_utc().to_offset(offset)
+    }
+}
+
+pub struct Config {
+    /// Location selected when adding a record
+    pub default_location: Option<String>,
+    /// Log type selected when adding a record
+    pub default_log_type: LogType,
+    pub first_weekday: Weekday,
+    pub workdays: Vec<Weekday>,
+    /// Format used to show and enter dates in the calendar
+    pub date_format: OwnedFormatItem,
+    pub timezone: Timezone,
+    /// Country whose public holidays are highlighted
+    pub country: Option<Country>,
+    /// Colour of the focussed panel
+    pub focus_color: Color,
+    /// What the colours of the work days in the calendar stand for
+    pub color_by: ColorBy,
+    /// Colours of log types, categories and locations by their lowercase name, replacing
+    /// the built-in ones
+    pub colors: HashMap<String, Color>,
+    pub keymap: Keymap,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            default_location: None,
+            default_log_type: LogType::Work,
+            first_weekday: Weekday::Monday,
+            workdays: vec![
+                Weekday::Monday,
+                Weekday::Tuesday,
+                Weekday::Wednesday,
+                Weekday::Thursday,
+                Weekday::Friday,
+            ],
+            date_format: format_description!("[year]-[month]-[day]").into(),
+            timezone: Timezone::Local,
+            country: None,
+            focus_color: Color::LightRed,
+            color_by: ColorBy::Category,
+            colors: HashMap::new(),
+            keymap: Keymap::default(),
+        }
+    }
+}
+
+/// The config file as it is written, every value is checked before it is used
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    default_location: Option<String>,
+    default_log_type: Option<String>,
+    first_weekday: Option<String>,
+    workdays: Option<Vec<String>>,
+    date_format: Option<String>,
+    timezone: Option<String>,
+    country: Option<String>,
+    focus_color: Option<String>,
+    color_by: Option<String>,
+    /// Colours by log type, category or location name, e.g. `[colors] home = "green"`
+    colors: HashMap<String, String>,
+    /// Key bindings per context and command, e.g. `[keys.calendar] next_day = ["l", "<Right>"]`
+    keys: HashMap<String, HashMap<String, KeyBindings>>,
+}
+
+fn parse_weekday(value: &str) -> Result<Weekday, String> {
+    match value.to_lowercase().as_str() {
+        "monday" | "mon" => Ok(Weekday::Monday),
+        "tuesday" | "tue" => Ok(Weekday::Tuesday),
+        "wednesday" | "wed" => Ok(Weekday::Wednesday),
+        "thursday" | "thu" => Ok(Weekday::Thursday),
+        "friday" | "fri" => Ok(Weekday::Friday),
+        "saturday" | "sat" => Ok(Weekday::Saturday),
+        "sunday" | "sun" => Ok(Weekday::Sunday),
+        _ => Err(format!("'{value}' is not a weekday")),
+    }
+}
+
+fn parse_timezone(value: &str) -> Result<Timezone, String> {
+    match value.to_lowercase().as_str() {
+        "utc" => Ok(Timezone::Utc),
+        "local" => Ok(Timezone::Local),
+        _ => UtcOffset::parse(
+            value,
+            format_description!("[offset_hour sign:mandatory]:[offset_minute]"),
+        )
+        .map(Timezone::Offset)
+        .map_err(|_| format!("'{value}' is not 'utc', 'local' or an offset like '+01:00'")),
+    }
+}
+
+impl ConfigFile {
+    fn into_config(self) -> Result<Config, String> {
+        let defaults = Config::default();
+        let field = |name: &'static str| move |e: String| format!("{name}: {e}");
+        Ok(Config {
+            default_location: self.default_location,
+            default_log_type: match self.default_log_type {
+                Some(value) => value.parse().map_err(field("default_log_type"))?,
+                None => defaults.default_log_type,
+            },
+            first_weekday: match self.first_weekday {
+                Some(value) => parse_weekday(&value).map_err(field("first_weekday"))?,
+                None => defaults.first_weekday,
+            },
+            workdays: match self.workdays {
+                Some(values) => values
+                    .iter()
+                    .map(|value| parse_weekday(value))
+                    .collect::<Result<_, _>>()
+                    .map_err(field("workdays"))?,
+                None => defaults.workdays,
+            },
+            date_format: match self.date_format {
+                Some(value) => format_description::parse_owned::<2>(&value)
+                    .map_err(|e| format!("date_format: '{value}' is not a valid format, {e}"))?,
+                None => defaults.date_format,
+            },
+            timezone: match self.timezone {
+                Some(value) => parse_timezone(&value).map_err(field("timezone"))?,
+                None => defaults.timezone,
+            },
+            country: match self.country {
+                Some(value) => Some(
+                    Country::from_str(&value, true)
+                        .map_err(|_| format!("country: '{value}' is not one of be, nl"))?,
+                ),
+                None => defaults.country,
+            },
+            focus_color: match self.focus_color {
+                Some(value) => Color::from_str(&value)
+                    .map_err(|_| format!("focus_color: '{value}' is not a colour"))?,
+                None => defaults.focus_color,
+            },
+            color_by: match self.color_by {
+                Some(value) => ColorBy::from_str(&value, true).map_err(|_| {
+                    format!("color_by: '{value}' is not one of category, location, log-type")
+                })?,
+                None => defaults.color_by,
+            },
+            colors: self
+                .colors
+                .into_iter()
+                .map(|(name, value)| match Color::from_str(&value) {
+                    Ok(color) => Ok((name.to_lowercase(), color)),
+                    Err(_) => Err(format!("colors: '{value}' of '{name}' is not a colour")),
+                })
+                .collect::<Result<_, _>>()?,
+            keymap: Keymap::with_overrides(self.keys).map_err(|e| format!("keys: {e}"))?,
+        })
+    }
+}
+
+impl Config {
+    /// Read the config file at `path`, or the one in the config folder when no path is given.
+    /// Only the latter may be missing, which gives the defaults.
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let path = match path {
+            Some(path) if !path.exists() => {
+                return Err(eyre!("config file {} does not exist", path.display()));
+            }
+            Some(path) => path.to_path_buf(),
+            None if !default_config_path().exists() => return Ok(Config::default()),
+            None => default_config_path(),
+        };
+        let content = fs::read_to_string(&path)
+            .map_err(|e| eyre!("could not read config file {}: {e}", path.display()))?;
+        let file: ConfigFile = toml::from_str(&content)
+            .map_err(|e| eyre!("invalid config file {}: {e}", path.display()))?;
+        file.into_config()
+            .map_err(|e| eyre!("invalid config file {}: {e}", path.display()))
+    }
+
+    pub fn is_workday(&self, weekday: Weekday) -> bool {
+        self.workdays.contains(&weekday)
+    }
+}
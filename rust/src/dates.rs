@@ -0,0 +1,151 @@
+use time::{
+    Date, Duration, Month, Weekday,
+    format_description::BorrowedFormatItem,
+    macros::{date, format_description},
+};
+
+use crate::config::config;
+
+/// The format used to show and enter dates, e.g. `2026-01-31`
+pub const DATE_FORMAT: &[BorrowedFormatItem<'static>] = format_description!("[year]-[month]-[day]");
+
+/// The first and last day the calendar can move to. A year is left on both sides for the
+/// surrounding weeks of the month grid and the weeks of the heatmap.
+pub const NAVIGABLE: (Date, Date) = (date!(-9998 - 01 - 01), date!(9998 - 12 - 31));
+
+/// The closest date to `date` the calendar can move to
+pub fn clamp_navigable(date: Date) -> Date {
+    date.clamp(NAVIGABLE.0, NAVIGABLE.1)
+}
+
+/// The first and last day of the month containing `date`
+pub fn month_bounds(date: Date) -> (Date, Date) {
+    let first = date.replace_day(1).unwrap();
+    let last = date.replace_day(date.month().length(date.year())).unwrap();
+    (first, last)
+}
+
+/// The first and last day of the year containing `date`
+pub fn year_bounds(date: Date) -> (Date, Date) {
+    let first = Date::from_calendar_date(date.year(), Month::January, 1).unwrap();
+    let last = Date::from_calendar_date(date.year(), Month::December, 31).unwrap();
+    (first, last)
+}
+
+/// The first day of the week containing `date`, weeks starting on `first_weekday`
+pub fn week_start(date: Date, first_weekday: Weekday) -> Date {
+    let offset = (7 + date.weekday().number_days_from_monday()
+        - first_weekday.number_days_from_monday())
+        % 7;
+    add_days(date, -i64::from(offset))
+}
+
+/// Move `days` days, staying at the first or last supported date instead of overflowing
+pub fn add_days(date: Date, days: i64) -> Date {
+    if days < 0 {
+        date.saturating_sub(Duration::days(-days))
+    } else {
+        date.saturating_add(Duration::days(days))
+    }
+}
+
+/// Move `months` months, the day is clamped to the length of the new month so that
+/// January 31st plus one month is the last day of February
+pub fn add_months(date: Date, months: i64) -> Date {
+    let bound = if months < 0 { Date::MIN } else { Date::MAX };
+    let index = (i64::from(date.year()) * 12 + i64::from(u8::from(date.month()) - 1))
+        .saturating_add(months);
+    let Ok(year) = i32::try_from(index.div_euclid(12)) else {
+        return bound;
+    };
+    let month = Month::try_from(index.rem_euclid(12) as u8 + 1).unwrap();
+    let day = date.day().min(month.length(year));
+    Date::from_calendar_date(year, month, day).unwrap_or(bound)
+}
+
+/// Move `years` years, February 29th becomes February 28th in other years
+pub fn add_years(date: Date, years: i64) -> Date {
+    add_months(date, years.saturating_mul(12))
+}
+
+/// Parse a `YYYY-MM-DD` string into a date
+pub fn parse_date(value: &str) -> Option<Date> {
+    Date::parse(value.trim(), DATE_FORMAT).ok()
+}
+
+/// Today in the configured timezone
+pub fn today() -> Date {
+    config().timezone.now().date()
+}
+
+/// Show a date in the configured date format
+pub fn display_date(date: Date) -> String {
+    date.format(&config().date_format)
+        .unwrap_or_else(|_| date.to_string())
+}
+
+/// Parse a date entered in the configured date format, or as `YYYY-MM-DD`
+pub fn parse_display_date(value: &str) -> Option<Date> {
+    Date::parse(value.trim(), &config().date_format)
+        .ok()
+        .or_else(|| parse_date(value))
+}
+
+/// Parse a `YYYY-MM` string into the first day of that month
+pub fn parse_month(value: &str) -> Option<Date> {
+    let (year, month) = value.trim().split_once('-')?;
+    let month: u8 = month.parse().ok()?;
+    Date::from_calendar_date(year.parse().ok()?, month.try_into().ok()?, 1).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_months_clamps_the_day() {
+        assert_eq!(add_months(date!(2026 - 01 - 31), 1), date!(2026 - 02 - 28));
+        assert_eq!(add_months(date!(2024 - 01 - 31), 1), date!(2024 - 02 - 29));
+        assert_eq!(add_months(date!(2026 - 03 - 31), -1), date!(2026 - 02 - 28));
+        assert_eq!(add_months(date!(2026 - 05 - 31), 1), date!(2026 - 06 - 30));
+        assert_eq!(add_months(date!(2026 - 11 - 15), 3), date!(2027 - 02 - 15));
+        assert_eq!(
+            add_months(date!(2026 - 02 - 15), -14),
+            date!(2024 - 12 - 15)
+        );
+        assert_eq!(add_years(date!(2024 - 02 - 29), 1), date!(2025 - 02 - 28));
+        assert_eq!(add_years(date!(2024 - 02 - 29), 4), date!(2028 - 02 - 29));
+    }
+
+    #[test]
+    fn add_months_saturates_at_the_limits() {
+        assert_eq!(add_months(date!(9999 - 11 - 30), 1), date!(9999 - 12 - 30));
+        assert_eq!(add_months(date!(9999 - 12 - 01), 1), Date::MAX);
+        assert_eq!(add_months(Date::MAX, i64::MAX), Date::MAX);
+        assert_eq!(add_months(date!(-9999 - 01 - 31), -1), Date::MIN);
+        assert_eq!(add_months(Date::MIN, i64::MIN), Date::MIN);
+        assert_eq!(add_years(date!(9999 - 06 - 15), 1), Date::MAX);
+        assert_eq!(add_years(date!(2026 - 06 - 15), i64::MAX), Date::MAX);
+        assert_eq!(add_years(date!(-9999 - 06 - 15), -1), Date::MIN);
+        assert_eq!(add_years(date!(2026 - 06 - 15), i64::MIN), Date::MIN);
+    }
+
+    #[test]
+    fn navigation_stays_in_range() {
+        assert_eq!(clamp_navigable(add_years(today(), 100_000)), NAVIGABLE.1);
+        assert_eq!(clamp_navigable(add_years(today(), -100_000)), NAVIGABLE.0);
+        assert_eq!(
+            clamp_navigable(date!(2026 - 10 - 18)),
+            date!(2026 - 10 - 18)
+        );
+    }
+
+    #[test]
+    fn week_start_on_any_weekday() {
+        // a Sunday
+        let day = date!(2026 - 10 - 18);
+        assert_eq!(week_start(day, Weekday::Monday), date!(2026 - 10 - 12));
+        assert_eq!(week_start(day, Weekday::Sunday), day);
+        assert_eq!(week_start(day, Weekday::Saturday), date!(2026 - 10 - 17));
+    }
+}
@@ -0,0 +1,270 @@
+use std::io::Write;
+
+use clap::ValueEnum;
+use color_eyre::{Result, eyre::eyre};
+use serde::Serialize;
+use time::{OffsetDateTime, format_description::BorrowedFormatItem, macros::format_description};
+
+use crate::store::{Location, RecordDetails};
+
+const ICS_DATE: &[BorrowedFormatItem<'static>] = format_description!("[year][month][day]");
+const ICS_TIMESTAMP: &[BorrowedFormatItem<'static>] =
+    format_description!("[year][month][day]T[hour][minute][second]Z");
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, ValueEnum)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+    /// iCalendar, every record becomes an all-day event (records only)
+    Ics,
+}
+
+/// A row of an export file, `HEADER` lists the names of its fields
+trait ExportRow: Serialize {
+    const HEADER: &'static [&'static str];
+}
+
+/// A record as it is written to an export file
+#[derive(Serialize)]
+struct RecordRow {
+    date: String,
+    location: String,
+    tag: String,
+    category: String,
+    log_type: String,
+    note: String,
+}
+
+impl ExportRow for RecordRow {
+    const HEADER: &'static [&'static str] =
+        &["date", "location", "tag", "category", "log_type", "note"];
+}
+
+impl From<&RecordDetails> for RecordRow {
+    fn from(record: &RecordDetails) -> Self {
+        RecordRow {
+            date: record.date.to_string(),
+            location: record.location.clone(),
+            tag: record.tag.clone(),
+            category: record.category.to_string(),
+            log_type: record.log_type.to_string(),
+            note: record.note.clone().unwrap_or_default(),
+        }
+    }
+}
+
+/// A location as it is written to an export file
+#[derive(Serialize)]
+struct LocationRow {
+    id: i64,
+    name: String,
+    tag: String,
+    category: String,
+    archived: bool,
+}
+
+impl ExportRow for LocationRow {
+    const HEADER: &'static [&'static str] = &["id", "name", "tag", "category", "archived"];
+}
+
+impl From<&Location> for LocationRow {
+    fn from(location: &Location) -> Self {
+        LocationRow {
+            id: location.id,
+            name: location.name.clone(),
+            tag: location.tag.clone(),
+            category: location.category.to_string(),
+            archived: location.archived,
+        }
+    }
+}
+
+fn write_rows<T: ExportRow>(rows: &[T], format: ExportFormat, out: impl Write) -> Result<()> {
+    match format {
+        ExportFormat::Csv => {
+            // the header is written by hand, an export without rows still has one
+            let mut writer = csv::WriterBuilder::new()
+                .has_headers(false)
+                .from_writer(out);
+            writer.write_record(T::HEADER)?;
+            for row in rows {
+                writer.serialize(row)?;
+            }
+            writer.flush()?;
+        }
+        ExportFormat::Json => {
+            let mut out = out;
+            serde_json::to_writer_pretty(&mut out, rows)?;
+            writeln!(out)?;
+        }
+        ExportFormat::Ics => return Err(eyre!("only records can be exported as ics")),
+    }
+    Ok(())
+}
+
+/// Escape a TEXT value (RFC 5545 3.3.11)
+fn ics_escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace(['\r', '\n'], "\\n")
+}
+
+/// Fold a content line into lines of at most 75 octets (RFC 5545 3.1)
+fn ics_fold(line: &str) -> String {
+    let mut folded = String::new();
+    let mut length = 0;
+    for c in line.chars() {
+        if length + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            length = 1;
+        }
+        folded.push(c);
+        length += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+/// Write the records as all-day events.
+///
+/// A day only has a single record, so the UID is derived from the date and the
+/// `calendar_id` of the database. Importing a newer export updates the events of re-logged
+/// days instead of duplicating them, exports of other databases get their own events.
+fn write_ics(records: &[RecordDetails], calendar_id: &str, mut out: impl Write) -> Result<()> {
+    let stamp = OffsetDateTime::now_utc().format(ICS_TIMESTAMP)?;
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//locwork//locwork//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+    ];
+    for record in records {
+        let next_day = record.date.next_day().unwrap_or(record.date);
+        lines.extend([
+            "BEGIN:VEVENT".to_string(),
+            format!("UID:{}-{calendar_id}@locwork", record.date),
+            format!("DTSTAMP:{stamp}"),
+            format!("DTSTART;VALUE=DATE:{}", record.date.format(ICS_DATE)?),
+            format!("DTEND;VALUE=DATE:{}", next_day.format(ICS_DATE)?),
+            format!(
+                "SUMMARY:{}",
+                ics_escape(&format!("{} ({})", record.location, record.log_type))
+            ),
+            format!("LOCATION:{}", ics_escape(&record.location)),
+            format!("CATEGORIES:{}", ics_escape(&record.log_type.to_string())),
+        ]);
+        if let Some(note) = &record.note {
+            lines.push(format!("DESCRIPTION:{}", ics_escape(note)));
+        }
+        lines.extend(["TRANSP:TRANSPARENT".to_string(), "END:VEVENT".to_string()]);
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    for line in lines {
+        out.write_all(ics_fold(&line).as_bytes())?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+            ExportFormat::Ics => "ics",
+        }
+    }
+}
+
+/// Write the records, `calendar_id` identifies the database in ics exports
+pub fn write_records(
+    records: &[RecordDetails],
+    format: ExportFormat,
+    calendar_id: &str,
+    out: impl Write,
+) -> Result<()> {
+    if let ExportFormat::Ics = format {
+        return write_ics(records, calendar_id, out);
+    }
+    let rows: Vec<RecordRow> = records.iter().map(RecordRow::from).collect();
+    write_rows(&rows, format, out)
+}
+
+pub fn write_locations(
+    locations: &[Location],
+    format: ExportFormat,
+    out: impl Write,
+) -> Result<()> {
+    let rows: Vec<LocationRow> = locations.iter().map(LocationRow::from).collect();
+    write_rows(&rows, format, out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::{Category, LogType};
+    use time::macros::date;
+
+    fn csv_export(records: &[RecordDetails]) -> String {
+        let mut out = Vec::new();
+        write_records(records, ExportFormat::Csv, "test", &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn empty_csv_export_has_a_header() {
+        assert_eq!(
+            csv_export(&[]),
+            "date,location,tag,category,log_type,note\n"
+        );
+
+        let mut out = Vec::new();
+        write_locations(&[], ExportFormat::Csv, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "id,name,tag,category,archived\n"
+        );
+    }
+
+    fn record() -> RecordDetails {
+        RecordDetails {
+            id: 1,
+            date: date!(2026 - 03 - 02),
+            log_type: LogType::Work,
+            note: Some("client visit".to_string()),
+            location: "Office".to_string(),
+            tag: "off".to_string(),
+            category: Category::Office,
+        }
+    }
+
+    #[test]
+    fn csv_header_matches_the_fields() {
+        let record = record();
+        let mut expected = csv::Writer::from_writer(Vec::new());
+        expected.serialize(RecordRow::from(&record)).unwrap();
+        let expected = String::from_utf8(expected.into_inner().unwrap()).unwrap();
+        assert_eq!(csv_export(&[record]), expected);
+    }
+
+    #[test]
+    fn ics_uid_contains_the_calendar_id() {
+        let mut out = Vec::new();
+        write_records(&[record()], ExportFormat::Ics, "0123abcd", &mut out).unwrap();
+        let ics = String::from_utf8(out).unwrap();
+        assert!(ics.contains("UID:2026-03-02-0123abcd@locwork\r\n"));
+    }
+
+    #[test]
+    fn ics_escapes_line_breaks() {
+        assert_eq!(ics_escape("a;b,c\\d"), "a\\;b\\,c\\\\d");
+        assert_eq!(
+            ics_escape("one\r\ntwo\rthree\nfour"),
+            "one\\ntwo\\nthree\\nfour"
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use std::ops::RangeInclusive;
+
+use clap::ValueEnum;
+use time::{Date, Duration, Month, Weekday};
+
+/// Countries with a built-in public holiday calendar
+#[derive(Clone, Copy, PartialEq, Eq, Debug, ValueEnum)]
+pub enum Country {
+    /// Belgium
+    Be,
+    /// The Netherlands
+    Nl,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Holiday {
+    pub date: Date,
+    pub name: &'static str,
+}
+
+/// How the date of a holiday is found in a given year
+enum Rule {
+    /// The same day every year
+    Fixed(Month, u8),
+    /// Like `Fixed`, but moved to the Saturday before when it falls on a Sunday
+    FixedNotSunday(Month, u8),
+    /// A number of days after (or before) Easter Sunday
+    Easter(i64),
+}
+
+const BE: &[(Rule, &str)] = &[
+    (Rule::Fixed(Month::January, 1), "New Year's Day"),
+    (Rule::Easter(1), "Easter Monday"),
+    (Rule::Fixed(Month::May, 1), "Labour Day"),
+    (Rule::Easter(39), "Ascension Day"),
+    (Rule::Easter(50), "Whit Monday"),
+    (Rule::Fixed(Month::July, 21), "National Day"),
+    (Rule::Fixed(Month::August, 15), "Assumption Day"),
+    (Rule::Fixed(Month::November, 1), "All Saints' Day"),
+    (Rule::Fixed(Month::November, 11), "Armistice Day"),
+    (Rule::Fixed(Month::December, 25), "Christmas Day"),
+];
+
+const NL: &[(Rule, &str)] = &[
+    (Rule::Fixed(Month::January, 1), "Nieuwjaarsdag"),
+    (Rule::Easter(-2), "Goede Vrijdag"),
+    (Rule::Easter(0), "Eerste Paasdag"),
+    (Rule::Easter(1), "Tweede Paasdag"),
+    (Rule::FixedNotSunday(Month::April, 27), "Koningsdag"),
+    (Rule::Fixed(Month::May, 5), "Bevrijdingsdag"),
+    (Rule::Easter(39), "Hemelvaartsdag"),
+    (Rule::Easter(49), "Eerste Pinksterdag"),
+    (Rule::Easter(50), "Tweede Pinksterdag"),
+    (Rule::Fixed(Month::December, 25), "Eerste Kerstdag"),
+    (Rule::Fixed(Month::December, 26), "Tweede Kerstdag"),
+];
+
+/// Years the gregorian calendar is used for, from its first full year on
+pub const YEARS: RangeInclusive<i32> = 1583..=9999;
+
+/// Easter Sunday of the gregorian calendar (anonymous gregorian algorithm), `None` outside
+/// of `YEARS`
+pub fn easter_sunday(year: i32) -> Option<Date> {
+    if !YEARS.contains(&year) {
+        return None;
+    }
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    Date::from_calendar_date(year, Month::try_from(month as u8).ok()?, day as u8).ok()
+}
+
+impl Rule {
+    fn date(&self, year: i32) -> Option<Date> {
+        match self {
+            Rule::Fixed(month, day) => Date::from_calendar_date(year, *month, *day).ok(),
+            Rule::FixedNotSunday(month, day) => {
+                let date = Date::from_calendar_date(year, *month, *day).ok()?;
+                if date.weekday() == Weekday::Sunday {
+                    date.previous_day()
+                } else {
+                    Some(date)
+                }
+            }
+            Rule::Easter(offset) => easter_sunday(year)?.checked_add(Duration::days(*offset)),
+        }
+    }
+}
+
+/// The public holidays of a country in a year, ordered by date, none outside of `YEARS`
+pub fn holidays(country: Country, year: i32) -> Vec<Holiday> {
+    if !YEARS.contains(&year) {
+        return Vec::new();
+    }
+    let rules = match country {
+        Country::Be => BE,
+        Country::Nl => NL,
+    };
+    let mut holidays: Vec<Holiday> = rules
+        .iter()
+        .filter_map(|(rule, name)| rule.date(year).map(|date| Holiday { date, name }))
+        .collect();
+    holidays.sort_by_key(|h| h.date);
+    holidays
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::date;
+
+    #[test]
+    fn easter_sunday_of_known_years() {
+        assert_eq!(easter_sunday(1583), Some(date!(1583 - 04 - 10)));
+        assert_eq!(easter_sunday(1818), Some(date!(1818 - 03 - 22)));
+        assert_eq!(easter_sunday(1943), Some(date!(1943 - 04 - 25)));
+        assert_eq!(easter_sunday(2000), Some(date!(2000 - 04 - 23)));
+        assert_eq!(easter_sunday(2024), Some(date!(2024 - 03 - 31)));
+        assert_eq!(easter_sunday(2026), Some(date!(2026 - 04 - 05)));
+        assert_eq!(easter_sunday(2038), Some(date!(2038 - 04 - 25)));
+    }
+
+    #[test]
+    fn no_easter_outside_the_gregorian_years() {
+        assert_eq!(easter_sunday(1582), None);
+        assert_eq!(easter_sunday(-5), None);
+        assert_eq!(easter_sunday(10000), None);
+        assert!(holidays(Country::Nl, 10000).is_empty());
+    }
+
+    #[test]
+    fn king_day_moves_to_saturday_when_on_sunday() {
+        // april 27th 2025 was a sunday
+        let names: Vec<(Date, &str)> = holidays(Country::Nl, 2025)
+            .iter()
+            .map(|h| (h.date, h.name))
+            .collect();
+        assert!(names.contains(&(date!(2025 - 04 - 26), "Koningsdag")));
+    }
+}
@@ -0,0 +1,236 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::{Result, eyre::eyre};
+
+use crate::{
+    dates::parse_date,
+    store::{Location, LogType, NamedRecord, Record, Store},
+};
+
+/// Folder the python version of locwork kept its `records.csv` and `locations` files in,
+/// the `user_data_dir` of platformdirs
+pub fn python_data_dir() -> Option<PathBuf> {
+    directories::BaseDirs::new().map(|dirs| {
+        let dir = dirs.data_local_dir().join("locwork");
+        // on windows platformdirs adds a folder for the app author, which defaults to the app name
+        if cfg!(windows) {
+            dir.join("locwork")
+        } else {
+            dir
+        }
+    })
+}
+
+/// A record that is already logged in locwork with a different location or type
+pub struct Conflict {
+    pub existing: NamedRecord,
+    pub imported: NamedRecord,
+}
+
+/// Everything an import would change, computed without writing to the store
+#[derive(Default)]
+pub struct ImportPlan {
+    /// Locations that do not exist in locwork yet
+    pub new_locations: Vec<String>,
+    /// Locations used by records but missing from the python locations file
+    pub unknown_locations: Vec<String>,
+    /// Records that will be written
+    pub records: Vec<NamedRecord>,
+    pub conflicts: Vec<Conflict>,
+    /// Records that are already logged exactly like this
+    pub unchanged: usize,
+    /// Rows of `records.csv` that could not be read
+    pub invalid_rows: Vec<String>,
+}
+
+/// Read the python `locations` file, one location per line.
+///
+/// A missing file has no locations, those of the records are created like unknown ones.
+pub fn read_python_locations(path: &Path) -> Result<Vec<String>> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(eyre!(
+                "could not read locations file {}: {e}",
+                path.display()
+            ));
+        }
+    };
+    Ok(content
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect())
+}
+
+/// Read the python `records.csv` (location, date, day_type).
+///
+/// Only the last record of a date is kept, like the python version did.
+/// Rows that can't be read are returned as messages instead of failing the import.
+pub fn read_python_records(path: &Path) -> Result<(Vec<NamedRecord>, Vec<String>)> {
+    let mut reader = csv::Reader::from_path(path)
+        .map_err(|e| eyre!("could not read records file {}: {e}", path.display()))?;
+    let mut by_date = BTreeMap::new();
+    let mut invalid_rows = Vec::new();
+    for (index, row) in reader.records().enumerate() {
+        // the header is line 1
+        let line = index + 2;
+        let row = match row {
+            Ok(row) => row,
+            Err(e) => {
+                invalid_rows.push(format!("line {line}: {e}"));
+                continue;
+            }
+        };
+        let (Some(location), Some(date), Some(day_type)) = (row.get(0), row.get(1), row.get(2))
+        else {
+            invalid_rows.push(format!("line {line}: expected location, date and day_type"));
+            continue;
+        };
+        let Some(date) = parse_date(date) else {
+            invalid_rows.push(format!("line {line}: '{date}' is not a date"));
+            continue;
+        };
+        // python DayType: FREE = 0, WORK = 1
+        let log_type = match day_type.trim() {
+            "0" => LogType::Holiday,
+            "1" => LogType::Work,
+            other => {
+                invalid_rows.push(format!("line {line}: unknown day_type '{other}'"));
+                continue;
+            }
+        };
+        by_date.insert(
+            date,
+            NamedRecord {
+                location: location.trim().to_string(),
+                date,
+                log_type,
+            },
+        );
+    }
+    Ok((by_date.into_values().collect(), invalid_rows))
+}
+
+fn named(record: &Record, locations: &[Location]) -> NamedRecord {
+    NamedRecord {
+        location: locations
+            .iter()
+            .find(|l| l.id == record.location_id)
+            .map(|l| l.name.clone())
+            .unwrap_or_default(),
+        date: record.date,
+        log_type: record.log_type.clone(),
+    }
+}
+
+fn quoted(names: &[String]) -> String {
+    names
+        .iter()
+        .map(|name| format!("'{name}'"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl ImportPlan {
+    /// Compare the python data with the store.
+    ///
+    /// Conflicting records are only written when `overwrite` is set.
+    pub async fn new(
+        store: &Store,
+        python_locations: Vec<String>,
+        python_records: Vec<NamedRecord>,
+        invalid_rows: Vec<String>,
+        overwrite: bool,
+    ) -> Result<Self> {
+        let locations = store.get_locations().await?;
+        let known: BTreeSet<&str> = locations.iter().map(|l| l.name.as_str()).collect();
+        let listed: BTreeSet<&str> = python_locations.iter().map(|l| l.as_str()).collect();
+        let used: BTreeSet<&str> = python_records.iter().map(|r| r.location.as_str()).collect();
+
+        let mut plan = ImportPlan {
+            new_locations: listed
+                .union(&used)
+                .filter(|name| !known.contains(*name))
+                .map(|name| name.to_string())
+                .collect(),
+            unknown_locations: used
+                .difference(&listed)
+                .map(|name| name.to_string())
+                .collect(),
+            invalid_rows,
+            ..Default::default()
+        };
+
+        let (Some(first), Some(last)) = (python_records.first(), python_records.last()) else {
+            return Ok(plan);
+        };
+        let existing: BTreeMap<_, _> = store
+            .get_records_between(first.date, last.date)
+            .await?
+            .iter()
+            .map(|r| (r.date, named(r, &locations)))
+            .collect();
+
+        for record in python_records {
+            match existing.get(&record.date) {
+                Some(current) if *current == record => plan.unchanged += 1,
+                Some(current) => {
+                    if overwrite {
+                        plan.records.push(record.clone());
+                    }
+                    plan.conflicts.push(Conflict {
+                        existing: current.clone(),
+                        imported: record,
+                    });
+                }
+                None => plan.records.push(record),
+            }
+        }
+        Ok(plan)
+    }
+
+    /// Print what the import does (or would do) to stdout
+    pub fn report(&self, overwrite: bool) {
+        if !self.new_locations.is_empty() {
+            println!("locations to create: {}", quoted(&self.new_locations));
+        }
+        if !self.unknown_locations.is_empty() {
+            println!(
+                "unknown locations (used by records, missing from the locations file): {}",
+                quoted(&self.unknown_locations)
+            );
+        }
+        if !self.conflicts.is_empty() {
+            let outcome = if overwrite { "overwritten" } else { "skipped" };
+            println!("conflicts with already logged days ({outcome}):");
+            for conflict in &self.conflicts {
+                println!(
+                    "  {}: {} ({}) -> {} ({})",
+                    conflict.existing.date,
+                    conflict.existing.location,
+                    conflict.existing.log_type,
+                    conflict.imported.location,
+                    conflict.imported.log_type
+                );
+            }
+        }
+        if !self.invalid_rows.is_empty() {
+            println!("invalid rows (skipped):");
+            for row in &self.invalid_rows {
+                println!("  {row}");
+            }
+        }
+        println!(
+            "records to import: {}, already logged: {}",
+            self.records.len(),
+            self.unchanged
+        );
+    }
+}
@@ -0,0 +1,848 @@
+use std::{collections::HashMap, fmt::Display};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+
+/// Where a key binding applies, the focussed panel or modal is looked up before `Global`
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum KeyContext {
+    Global,
+    Calendar,
+    Locations,
+    Status,
+    RecordModal,
+    /// The modals adding or editing a location, typed characters go into their fields
+    LocationModal,
+    /// A yes/no question inside a modal
+    Prompt,
+    /// A list to pick a value from inside a modal
+    Picker,
+    /// The overlay listing the key bindings
+    Help,
+}
+
+/// Name used in the config file and title shown in the help of every context
+const CONTEXTS: &[(KeyContext, &str, &str)] = &[
+    (KeyContext::Global, "global", "Everywhere"),
+    (KeyContext::Calendar, "calendar", "Calendar"),
+    (KeyContext::Locations, "locations", "Locations"),
+    (KeyContext::Status, "status", "Statistics"),
+    (KeyContext::RecordModal, "record_modal", "Add New Log"),
+    (KeyContext::LocationModal, "location_modal", "Location"),
+    (KeyContext::Prompt, "prompt", "Question"),
+    (KeyContext::Picker, "picker", "List"),
+    (KeyContext::Help, "help", "Help"),
+];
+
+impl KeyContext {
+    pub fn name(&self) -> &'static str {
+        CONTEXTS.iter().find(|(c, _, _)| c == self).unwrap().1
+    }
+
+    pub fn title(&self) -> &'static str {
+        CONTEXTS.iter().find(|(c, _, _)| c == self).unwrap().2
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        CONTEXTS
+            .iter()
+            .find(|(_, n, _)| *n == name)
+            .map(|(c, _, _)| *c)
+    }
+
+    /// The contexts whose bindings are looked up while this one is active, in order. The
+    /// panels fall back to the global bindings, modals and overlays only use their own.
+    pub fn lookup(&self) -> Vec<KeyContext> {
+        match self {
+            KeyContext::Calendar | KeyContext::Locations | KeyContext::Status => {
+                vec![*self, KeyContext::Global]
+            }
+            _ => vec![*self],
+        }
+    }
+
+    /// Whether digits typed in this context form a count, in text fields they are typed
+    pub fn takes_counts(&self) -> bool {
+        *self != KeyContext::LocationModal
+    }
+}
+
+/// Everything a key can be bound to
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Command {
+    Quit,
+    FocusLocations,
+    FocusCalendar,
+    FocusStatus,
+    NextDay,
+    PrevDay,
+    NextWeek,
+    PrevWeek,
+    NextMonth,
+    PrevMonth,
+    NextYear,
+    PrevYear,
+    GotoDate,
+    Today,
+    CycleColors,
+    ToggleYear,
+    ToggleHeatmap,
+    ToggleWeek,
+    AddRecord,
+    DeleteRecord,
+    SelectNext,
+    SelectPrev,
+    SelectFirst,
+    SelectLast,
+    AddLocation,
+    DeleteLocation,
+    EditLocation,
+    ArchiveLocation,
+    ShowArchived,
+    EditDate,
+    EditNote,
+    PickLocation,
+    PickType,
+    NextField,
+    PrevField,
+    NextCategory,
+    PrevCategory,
+    Confirm,
+    Cancel,
+    Help,
+    OpenCommandLine,
+    Dismiss,
+    Undo,
+}
+
+/// Name used in the config file and a short description of every command
+const COMMANDS: &[(Command, &str, &str)] = &[
+    (Command::Quit, "quit", "quit locwork"),
+    (
+        Command::FocusLocations,
+        "focus_locations",
+        "focus the locations",
+    ),
+    (
+        Command::FocusCalendar,
+        "focus_calendar",
+        "focus the calendar",
+    ),
+    (Command::FocusStatus, "focus_status", "focus the statistics"),
+    (Command::NextDay, "next_day", "next day"),
+    (Command::PrevDay, "prev_day", "previous day"),
+    (Command::NextWeek, "next_week", "next week"),
+    (Command::PrevWeek, "prev_week", "previous week"),
+    (Command::NextMonth, "next_month", "next month"),
+    (Command::PrevMonth, "prev_month", "previous month"),
+    (Command::NextYear, "next_year", "next year"),
+    (Command::PrevYear, "prev_year", "previous year"),
+    (Command::GotoDate, "goto_date", "go to a date or month"),
+    (Command::Today, "today", "go to today"),
+    (
+        Command::CycleColors,
+        "cycle_colors",
+        "colour by category, location or log type",
+    ),
+    (
+        Command::ToggleYear,
+        "toggle_year",
+        "switch between the month and the year",
+    ),
+    (
+        Command::ToggleHeatmap,
+        "toggle_heatmap",
+        "switch between the month and the heatmap of the last weeks",
+    ),
+    (
+        Command::ToggleWeek,
+        "toggle_week",
+        "switch between the month and the days of the week",
+    ),
+    (Command::AddRecord, "add_record", "log the selected day"),
+    (
+        Command::DeleteRecord,
+        "delete_record",
+        "delete the record of the selected day",
+    ),
+    (Command::SelectNext, "select_next", "select next"),
+    (Command::SelectPrev, "select_prev", "select previous"),
+    (Command::SelectFirst, "select_first", "select first"),
+    (Command::SelectLast, "select_last", "select last"),
+    (Command::AddLocation, "add_location", "add a location"),
+    (
+        Command::DeleteLocation,
+        "delete_location",
+        "delete the selected location",
+    ),
+    (
+        Command::EditLocation,
+        "edit_location",
+        "rename the selected location or change its tag",
+    ),
+    (
+        Command::ArchiveLocation,
+        "archive_location",
+        "archive or unarchive the selected location",
+    ),
+    (
+        Command::ShowArchived,
+        "show_archived",
+        "show or hide the archived locations",
+    ),
+    (Command::EditDate, "edit_date", "edit the date"),
+    (Command::EditNote, "edit_note", "edit the note of the day"),
+    (Command::PickLocation, "pick_location", "pick the location"),
+    (Command::PickType, "pick_type", "pick the log type"),
+    (Command::NextField, "next_field", "next field"),
+    (Command::PrevField, "prev_field", "previous field"),
+    (Command::NextCategory, "next_category", "next category"),
+    (Command::PrevCategory, "prev_category", "previous category"),
+    (Command::Confirm, "confirm", "confirm"),
+    (Command::Cancel, "cancel", "cancel"),
+    (Command::Help, "help", "show the key bindings"),
+    (Command::OpenCommandLine, "command_line", "enter a command"),
+    (Command::Dismiss, "dismiss", "dismiss the notifications"),
+    (Command::Undo, "undo", "undo the last deletion"),
+];
+
+impl Command {
+    pub fn name(&self) -> &'static str {
+        COMMANDS.iter().find(|(c, _, _)| c == self).unwrap().1
+    }
+
+    pub fn description(&self) -> &'static str {
+        COMMANDS.iter().find(|(c, _, _)| c == self).unwrap().2
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        COMMANDS
+            .iter()
+            .find(|(_, n, _)| *n == name)
+            .map(|(c, _, _)| *c)
+    }
+}
+
+/// A single key with its modifiers, shift is part of the character for `KeyCode::Char`
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct KeyPress {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl From<KeyEvent> for KeyPress {
+    fn from(event: KeyEvent) -> Self {
+        let mut modifiers = event.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT);
+        if !matches!(event.code, KeyCode::Char(_) | KeyCode::BackTab) {
+            modifiers |= event.modifiers & KeyModifiers::SHIFT;
+        }
+        KeyPress {
+            code: event.code,
+            modifiers,
+        }
+    }
+}
+
+const NAMED_KEYS: &[(&str, KeyCode)] = &[
+    ("esc", KeyCode::Esc),
+    ("enter", KeyCode::Enter),
+    ("cr", KeyCode::Enter),
+    ("tab", KeyCode::Tab),
+    ("backtab", KeyCode::BackTab),
+    ("bs", KeyCode::Backspace),
+    ("space", KeyCode::Char(' ')),
+    ("lt", KeyCode::Char('<')),
+    ("left", KeyCode::Left),
+    ("right", KeyCode::Right),
+    ("up", KeyCode::Up),
+    ("down", KeyCode::Down),
+    ("home", KeyCode::Home),
+    ("end", KeyCode::End),
+    ("pageup", KeyCode::PageUp),
+    ("pagedown", KeyCode::PageDown),
+    ("del", KeyCode::Delete),
+];
+
+impl KeyPress {
+    fn char(c: char) -> Self {
+        KeyPress {
+            code: KeyCode::Char(c),
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    /// Parse the inside of a `<...>` key, e.g. `C-d`, `Esc` or `A-Left`
+    fn parse_special(name: &str) -> Option<Self> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = name;
+        while rest.len() > 2 && rest.as_bytes()[1] == b'-' {
+            match rest.as_bytes()[0].to_ascii_lowercase() {
+                b'c' => modifiers |= KeyModifiers::CONTROL,
+                b'a' | b'm' => modifiers |= KeyModifiers::ALT,
+                b's' => modifiers |= KeyModifiers::SHIFT,
+                _ => return None,
+            }
+            rest = &rest[2..];
+        }
+        let code = if rest.chars().count() == 1 {
+            KeyCode::Char(rest.chars().next()?)
+        } else if let Some(n) = rest.strip_prefix(['f', 'F'])
+            && let Ok(n) = n.parse::<u8>()
+            && (1..=12).contains(&n)
+        {
+            KeyCode::F(n)
+        } else {
+            let lower = rest.to_lowercase();
+            NAMED_KEYS.iter().find(|(n, _)| *n == lower)?.1
+        };
+        if let KeyCode::Char(_) | KeyCode::BackTab = code {
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+        Some(KeyPress { code, modifiers })
+    }
+}
+
+impl Display for KeyPress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut prefix = String::new();
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            prefix.push_str("C-");
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            prefix.push_str("A-");
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            prefix.push_str("S-");
+        }
+        let name = match self.code {
+            KeyCode::Char('<') => "lt".to_string(),
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char(c) if prefix.is_empty() => return write!(f, "{c}"),
+            KeyCode::Char(c) => c.to_string(),
+            KeyCode::Esc => "Esc".to_string(),
+            KeyCode::Enter => "Enter".to_string(),
+            KeyCode::Backspace => "BS".to_string(),
+            KeyCode::PageUp => "PageUp".to_string(),
+            KeyCode::PageDown => "PageDown".to_string(),
+            KeyCode::Delete => "Del".to_string(),
+            KeyCode::F(n) => format!("F{n}"),
+            other => format!("{other:?}"),
+        };
+        write!(f, "<{prefix}{name}>")
+    }
+}
+
+/// A sequence of keys like `gg`, `<C-d>` or `dd`
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct KeySequence(pub Vec<KeyPress>);
+
+impl KeySequence {
+    pub fn parse(notation: &str) -> Result<Self, String> {
+        let mut keys = Vec::new();
+        let mut chars = notation.chars();
+        while let Some(c) = chars.next() {
+            if c == '<' {
+                let name: String = chars.by_ref().take_while(|c| *c != '>').collect();
+                let key =
+                    KeyPress::parse_special(&name).ok_or(format!("'<{name}>' is not a key"))?;
+                keys.push(key);
+            } else {
+                keys.push(KeyPress::char(c));
+            }
+        }
+        if keys.is_empty() {
+            return Err("empty key binding".to_string());
+        }
+        Ok(KeySequence(keys))
+    }
+}
+
+impl Display for KeySequence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for key in &self.0 {
+            write!(f, "{key}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Key bindings of a config file, a single key sequence or a list of them per command
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum KeyBindings {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// Named commands bound to key sequences, per context
+pub struct Keymap {
+    bindings: HashMap<KeyContext, Vec<(KeySequence, Command)>>,
+}
+
+const DEFAULT_BINDINGS: &[(KeyContext, &str, Command)] = &[
+    (KeyContext::Global, "q", Command::Quit),
+    (KeyContext::Global, "1", Command::FocusLocations),
+    (KeyContext::Global, "2", Command::FocusCalendar),
+    (KeyContext::Global, "3", Command::FocusStatus),
+    (KeyContext::Global, "?", Command::Help),
+    (KeyContext::Global, ":", Command::OpenCommandLine),
+    (KeyContext::Global, "<Esc>", Command::Dismiss),
+    (KeyContext::Calendar, "l", Command::NextDay),
+    (KeyContext::Calendar, "h", Command::PrevDay),
+    (KeyContext::Calendar, "j", Command::NextWeek),
+    (KeyContext::Calendar, "k", Command::PrevWeek),
+    (KeyContext::Calendar, "L", Command::NextMonth),
+    (KeyContext::Calendar, "H", Command::PrevMonth),
+    (KeyContext::Calendar, "]", Command::NextYear),
+    (KeyContext::Calendar, "[", Command::PrevYear),
+    (KeyContext::Calendar, "g", Command::GotoDate),
+    (KeyContext::Calendar, "t", Command::Today),
+    (KeyContext::Calendar, "c", Command::CycleColors),
+    (KeyContext::Calendar, "y", Command::ToggleYear),
+    (KeyContext::Calendar, "m", Command::ToggleHeatmap),
+    (KeyContext::Calendar, "w", Command::ToggleWeek),
+    (KeyContext::Calendar, "<Enter>", Command::Confirm),
+    (KeyContext::Calendar, "A", Command::AddRecord),
+    (KeyContext::Calendar, "D", Command::DeleteRecord),
+    (KeyContext::Calendar, "dd", Command::DeleteRecord),
+    (KeyContext::Calendar, "u", Command::Undo),
+    (KeyContext::Locations, "j", Command::SelectNext),
+    (KeyContext::Locations, "k", Command::SelectPrev),
+    (KeyContext::Locations, "gg", Command::SelectFirst),
+    (KeyContext::Locations, "G", Command::SelectLast),
+    (KeyContext::Locations, "A", Command::AddLocation),
+    (KeyContext::Locations, "D", Command::DeleteLocation),
+    (KeyContext::Locations, "dd", Command::DeleteLocation),
+    (KeyContext::Locations, "e", Command::EditLocation),
+    (KeyContext::Locations, "u", Command::Undo),
+    (KeyContext::Locations, "x", Command::ArchiveLocation),
+    (KeyContext::Locations, ".", Command::ShowArchived),
+    (KeyContext::RecordModal, "d", Command::EditDate),
+    (KeyContext::RecordModal, "l", Command::PickLocation),
+    (KeyContext::RecordModal, "t", Command::PickType),
+    (KeyContext::RecordModal, "n", Command::EditNote),
+    (KeyContext::RecordModal, "<Enter>", Command::Confirm),
+    (KeyContext::RecordModal, "<Esc>", Command::Cancel),
+    (KeyContext::RecordModal, "q", Command::Cancel),
+    (KeyContext::RecordModal, "?", Command::Help),
+    (KeyContext::LocationModal, "<Enter>", Command::Confirm),
+    (KeyContext::LocationModal, "<Esc>", Command::Cancel),
+    (KeyContext::LocationModal, "<Tab>", Command::NextField),
+    (KeyContext::LocationModal, "<Down>", Command::NextField),
+    (KeyContext::LocationModal, "<BackTab>", Command::PrevField),
+    (KeyContext::LocationModal, "<Up>", Command::PrevField),
+    (KeyContext::LocationModal, "<Right>", Command::NextCategory),
+    (KeyContext::LocationModal, "<Left>", Command::PrevCategory),
+    (KeyContext::LocationModal, "<F1>", Command::Help),
+    (KeyContext::Prompt, "y", Command::Confirm),
+    (KeyContext::Prompt, "<Enter>", Command::Confirm),
+    (KeyContext::Prompt, "n", Command::Cancel),
+    (KeyContext::Prompt, "<Esc>", Command::Cancel),
+    (KeyContext::Prompt, "?", Command::Help),
+    (KeyContext::Picker, "j", Command::SelectNext),
+    (KeyContext::Picker, "k", Command::SelectPrev),
+    (KeyContext::Picker, "gg", Command::SelectFirst),
+    (KeyContext::Picker, "G", Command::SelectLast),
+    (KeyContext::Picker, "<Enter>", Command::Confirm),
+    (KeyContext::Picker, "<Esc>", Command::Cancel),
+    (KeyContext::Picker, "q", Command::Cancel),
+    (KeyContext::Picker, "?", Command::Help),
+    (KeyContext::Help, "j", Command::SelectNext),
+    (KeyContext::Help, "k", Command::SelectPrev),
+    (KeyContext::Help, "<Esc>", Command::Cancel),
+    (KeyContext::Help, "q", Command::Cancel),
+    (KeyContext::Help, "?", Command::Cancel),
+];
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let mut bindings: HashMap<KeyContext, Vec<(KeySequence, Command)>> = HashMap::new();
+        for (context, notation, command) in DEFAULT_BINDINGS {
+            let sequence = KeySequence::parse(notation).unwrap();
+            bindings
+                .entry(*context)
+                .or_default()
+                .push((sequence, *command));
+        }
+        Keymap { bindings }
+    }
+}
+
+impl Keymap {
+    /// The default keymap where every command named in `overrides` gets the given keys
+    /// instead of its default keys, per context
+    pub fn with_overrides(
+        overrides: HashMap<String, HashMap<String, KeyBindings>>,
+    ) -> Result<Self, String> {
+        let mut keymap = Keymap::default();
+        for (context_name, commands) in overrides {
+            let context = KeyContext::from_name(&context_name).ok_or(format!(
+                "unknown context '{context_name}', expected one of {}",
+                CONTEXTS
+                    .iter()
+                    .map(|(_, n, _)| *n)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))?;
+            for (command_name, keys) in commands {
+                let command = Command::from_name(&command_name).ok_or(format!(
+                    "unknown command '{command_name}' in '{context_name}'"
+                ))?;
+                let notations = match keys {
+                    KeyBindings::One(notation) => vec![notation],
+                    KeyBindings::Many(notations) => notations,
+                };
+                let sequences = notations
+                    .iter()
+                    .map(|notation| Ok((KeySequence::parse(notation)?, command)))
+                    .collect::<Result<Vec<_>, String>>()
+                    .map_err(|e| format!("{context_name}.{command_name}: {e}"))?;
+                let bindings = keymap.bindings.entry(context).or_default();
+                // keep the place of the default keys, so the help stays in the same order
+                let position = bindings
+                    .iter()
+                    .position(|(_, c)| *c == command)
+                    .unwrap_or(bindings.len());
+                bindings.retain(|(_, c)| *c != command);
+                bindings.splice(position..position, sequences);
+            }
+        }
+        keymap.check_ambiguity()?;
+        Ok(keymap)
+    }
+
+    /// Keys can't be both a binding and the start of a longer one that is looked up at the
+    /// same time, there is no timeout to tell `d` from the start of `dd`
+    fn check_ambiguity(&self) -> Result<(), String> {
+        for (context, name, _) in CONTEXTS {
+            let bindings: Vec<&(KeySequence, Command)> = context
+                .lookup()
+                .iter()
+                .flat_map(|c| self.bindings(*c))
+                .collect();
+            for (short, short_command) in &bindings {
+                if let Some((long, long_command)) = bindings
+                    .iter()
+                    .find(|(long, _)| long.0.len() > short.0.len() && long.0.starts_with(&short.0))
+                {
+                    return Err(format!(
+                        "'{short}' of {} can't be used in {name}, it is also the start of '{long}' of {}",
+                        short_command.name(),
+                        long_command.name()
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The first key sequence bound to `command` in `context`, e.g. for a help line
+    pub fn key_for(&self, context: KeyContext, command: Command) -> Option<&KeySequence> {
+        self.bindings(context)
+            .iter()
+            .find(|(_, c)| *c == command)
+            .map(|(sequence, _)| sequence)
+    }
+
+    /// The bindings of a single context in the order they were defined
+    pub fn bindings(&self, context: KeyContext) -> &[(KeySequence, Command)] {
+        self.bindings
+            .get(&context)
+            .map(|b| b.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Every command bound in a context once, with all of its keys
+    pub fn commands(&self, context: KeyContext) -> Vec<(Command, Vec<String>)> {
+        let mut commands: Vec<(Command, Vec<String>)> = Vec::new();
+        for (sequence, command) in self.bindings(context) {
+            match commands.iter_mut().find(|(c, _)| c == command) {
+                Some((_, keys)) => keys.push(sequence.to_string()),
+                None => commands.push((*command, vec![sequence.to_string()])),
+            }
+        }
+        commands
+    }
+}
+
+/// Result of feeding a key to the [`KeyDispatcher`]
+#[derive(PartialEq, Eq, Debug)]
+pub enum Dispatch {
+    /// The keys so far are the start of a binding or a count
+    Pending,
+    /// A command with its count prefix, 1 when no count was typed
+    Matched(Command, u32),
+    /// The keys are not bound
+    Unmatched,
+}
+
+/// Collects keys until they form a bound sequence, with an optional count prefix like `5l`
+#[derive(Default)]
+pub struct KeyDispatcher {
+    pending: Vec<KeyPress>,
+    count: Option<u32>,
+}
+
+impl KeyDispatcher {
+    pub fn reset(&mut self) {
+        self.pending.clear();
+        self.count = None;
+    }
+
+    /// The keys typed so far, e.g. `5g`
+    pub fn pending(&self) -> String {
+        let count = self.count.map(|c| c.to_string()).unwrap_or_default();
+        format!("{count}{}", KeySequence(self.pending.clone()))
+    }
+
+    /// Feed a key, `contexts` are searched in order so the first one wins
+    pub fn feed(&mut self, key: KeyEvent, keymap: &Keymap, contexts: &[KeyContext]) -> Dispatch {
+        let press = KeyPress::from(key);
+
+        // A digit starts or continues a count, unless the digit itself is bound
+        if self.pending.is_empty()
+            && contexts.iter().all(KeyContext::takes_counts)
+            && press.modifiers.is_empty()
+            && let KeyCode::Char(c) = press.code
+            && let Some(digit) = c.to_digit(10)
+        {
+            let bound = self.lookup(keymap, contexts, &[press]).0.is_some();
+            if self.count.is_some() || (!bound && digit != 0) {
+                self.count = Some((self.count.unwrap_or(0) * 10 + digit).min(9999));
+                return Dispatch::Pending;
+            }
+        }
+
+        self.pending.push(press);
+        let (exact, is_prefix) = self.lookup(keymap, contexts, &self.pending);
+        match (exact, is_prefix) {
+            // the keymap has no binding that also starts a longer one, see `check_ambiguity`
+            (_, true) => Dispatch::Pending,
+            (Some(command), false) => {
+                let count = self.count.unwrap_or(1);
+                self.reset();
+                Dispatch::Matched(command, count)
+            }
+            (None, false) => {
+                let retry = self.pending.len() > 1;
+                self.reset();
+                if retry {
+                    // start over with the key that broke the sequence
+                    return self.feed(key, keymap, contexts);
+                }
+                Dispatch::Unmatched
+            }
+        }
+    }
+
+    /// The command bound to exactly `keys`, and whether `keys` starts a longer binding
+    fn lookup(
+        &self,
+        keymap: &Keymap,
+        contexts: &[KeyContext],
+        keys: &[KeyPress],
+    ) -> (Option<Command>, bool) {
+        let mut exact = None;
+        let mut is_prefix = false;
+        for context in contexts {
+            for (sequence, command) in keymap.bindings(*context) {
+                if sequence.0 == keys {
+                    exact = exact.or(Some(*command));
+                } else if sequence.0.starts_with(keys) {
+                    is_prefix = true;
+                }
+            }
+        }
+        (exact, is_prefix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed_all(keys: &str, contexts: &[KeyContext]) -> Vec<Dispatch> {
+        let keymap = Keymap::default();
+        let mut dispatcher = KeyDispatcher::default();
+        keys.chars()
+            .map(|c| {
+                let event = KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+                dispatcher.feed(event, &keymap, contexts)
+            })
+            .collect()
+    }
+
+    fn last(keys: &str) -> Dispatch {
+        feed_all(keys, &KeyContext::Calendar.lookup())
+            .pop()
+            .unwrap()
+    }
+
+    #[test]
+    fn single_keys_and_counts() {
+        assert_eq!(last("l"), Dispatch::Matched(Command::NextDay, 1));
+        assert_eq!(last("5l"), Dispatch::Matched(Command::NextDay, 5));
+        assert_eq!(last("50j"), Dispatch::Matched(Command::NextWeek, 50));
+        assert_eq!(
+            feed_all("45", &KeyContext::Calendar.lookup()),
+            vec![Dispatch::Pending, Dispatch::Pending]
+        );
+    }
+
+    #[test]
+    fn bound_digits_are_no_count() {
+        // the focus keys are global bindings
+        assert_eq!(last("2"), Dispatch::Matched(Command::FocusCalendar, 1));
+        // a zero can't start a count
+        assert_eq!(last("0"), Dispatch::Unmatched);
+    }
+
+    #[test]
+    fn sequences() {
+        assert_eq!(
+            feed_all("dd", &KeyContext::Calendar.lookup()),
+            vec![
+                Dispatch::Pending,
+                Dispatch::Matched(Command::DeleteRecord, 1)
+            ]
+        );
+        assert_eq!(last("4dd"), Dispatch::Matched(Command::DeleteRecord, 4));
+        assert_eq!(
+            feed_all("gg", &KeyContext::Locations.lookup()).pop(),
+            Some(Dispatch::Matched(Command::SelectFirst, 1))
+        );
+    }
+
+    #[test]
+    fn a_broken_sequence_starts_over_with_the_last_key() {
+        assert_eq!(last("dl"), Dispatch::Matched(Command::NextDay, 1));
+        assert_eq!(last("dx"), Dispatch::Unmatched);
+        // the count is dropped with the broken sequence
+        assert_eq!(last("4dl"), Dispatch::Matched(Command::NextDay, 1));
+    }
+
+    #[test]
+    fn digits_are_typed_in_text_fields() {
+        assert_eq!(
+            feed_all("B42", &[KeyContext::LocationModal]),
+            vec![
+                Dispatch::Unmatched,
+                Dispatch::Unmatched,
+                Dispatch::Unmatched
+            ]
+        );
+        let keymap = Keymap::default();
+        let mut dispatcher = KeyDispatcher::default();
+        dispatcher.feed(
+            KeyEvent::new(KeyCode::Char('4'), KeyModifiers::NONE),
+            &keymap,
+            &[KeyContext::LocationModal],
+        );
+        assert_eq!(
+            dispatcher.feed(
+                KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
+                &keymap,
+                &[KeyContext::LocationModal]
+            ),
+            Dispatch::Matched(Command::Confirm, 1)
+        );
+    }
+
+    #[test]
+    fn contexts_are_searched_in_order() {
+        // `q` cancels in the record modal, and quits from a panel
+        assert_eq!(
+            feed_all("q", &[KeyContext::RecordModal]).pop(),
+            Some(Dispatch::Matched(Command::Cancel, 1))
+        );
+        assert_eq!(last("q"), Dispatch::Matched(Command::Quit, 1));
+    }
+
+    #[test]
+    fn parse_key_sequences() {
+        let sequence = KeySequence::parse("<C-d>g<Esc>").unwrap();
+        assert_eq!(sequence.0.len(), 3);
+        assert_eq!(sequence.0[0].modifiers, KeyModifiers::CONTROL);
+        assert_eq!(sequence.to_string(), "<C-d>g<Esc>");
+        assert!(KeySequence::parse("<nope>").is_err());
+        assert!(KeySequence::parse("").is_err());
+    }
+
+    fn overrides(
+        context: &str,
+        command: &str,
+        keys: &str,
+    ) -> HashMap<String, HashMap<String, KeyBindings>> {
+        HashMap::from([(
+            context.to_string(),
+            HashMap::from([(command.to_string(), KeyBindings::One(keys.to_string()))]),
+        )])
+    }
+
+    /// The default bindings as the markdown listed in the README
+    fn readme_keys() -> String {
+        let keymap = Keymap::default();
+        let mut text = String::new();
+        for (context, name, title) in CONTEXTS {
+            let commands = keymap.commands(*context);
+            if commands.is_empty() {
+                continue;
+            }
+            text.push_str(&format!("\n**{title}** (`[keys.{name}]`)\n\n"));
+            text.push_str("| Keys | Command | Description |\n|---|---|---|\n");
+            for (command, keys) in commands {
+                let keys: Vec<String> = keys.iter().map(|k| format!("`{k}`")).collect();
+                text.push_str(&format!(
+                    "| {} | `{}` | {} |\n",
+                    keys.join(" "),
+                    command.name(),
+                    command.description()
+                ));
+            }
+        }
+        text
+    }
+
+    #[test]
+    fn readme_lists_the_default_bindings() {
+        let readme = include_str!("../README.md");
+        let start = "<!-- default keys -->\n";
+        let end = "<!-- end of default keys -->";
+        let listed = readme
+            .split_once(start)
+            .and_then(|(_, rest)| rest.split_once(end))
+            .map(|(listed, _)| listed);
+        let expected = readme_keys();
+        assert!(
+            listed == Some(expected.as_str()),
+            "README.md lists outdated default keys, put this between the markers:\n{expected}"
+        );
+    }
+
+    #[test]
+    fn default_bindings_are_unambiguous() {
+        assert_eq!(Keymap::default().check_ambiguity(), Ok(()));
+    }
+
+    #[test]
+    fn overrides_replace_the_default_keys() {
+        let keymap = Keymap::with_overrides(overrides("calendar", "next_day", "n")).unwrap();
+        let keys: Vec<String> = keymap
+            .bindings(KeyContext::Calendar)
+            .iter()
+            .filter(|(_, c)| *c == Command::NextDay)
+            .map(|(s, _)| s.to_string())
+            .collect();
+        assert_eq!(keys, vec!["n"]);
+    }
+
+    #[test]
+    fn ambiguous_overrides_are_rejected() {
+        let error = Keymap::with_overrides(overrides("calendar", "goto_date", "d")).err();
+        assert!(error.is_some_and(|e| e.contains("'d' of goto_date") && e.contains("'dd'")));
+        // a global key can't start a panel's sequence either
+        assert!(Keymap::with_overrides(overrides("global", "quit", "d")).is_err());
+        // the record modal doesn't use the global keys
+        assert!(Keymap::with_overrides(overrides("global", "quit", "dq")).is_ok());
+    }
+}
@@ -0,0 +1,321 @@
+use std::path::PathBuf;
+
+use clap::ValueEnum;
+use time::Date;
+
+use crate::{
+    config::config,
+    dates::{parse_display_date, parse_month, today},
+    export::ExportFormat,
+    store::{Category, Location, LogType},
+};
+
+/// A command typed on the `:` command line
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum LineCommand {
+    /// `:log <date> <location> [type]`
+    Log {
+        date: Date,
+        location: String,
+        log_type: LogType,
+    },
+    /// `:goto <date>` or `:goto <YYYY-MM>`
+    Goto(Date),
+    /// `:loc add <name> [tag]`
+    AddLocation { name: String, tag: Option<String> },
+    /// `:loc remove <name>`
+    RemoveLocation(String),
+    /// `:loc category <name> <category>`
+    SetCategory { name: String, category: Category },
+    /// `:loc archive <name>` or `:loc unarchive <name>`
+    ArchiveLocation { name: String, archived: bool },
+    /// `:export <csv|json|ics> [path]`
+    Export { format: ExportFormat, path: PathBuf },
+    /// `:q` or `:quit`
+    Quit,
+}
+
+const COMMANDS: &[&str] = &["log", "goto", "loc", "export", "quit"];
+
+/// Split a line into words, text between double quotes is a single word
+pub fn split_words(line: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut quoted = false;
+    for c in line.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                in_word = true;
+            }
+            c if c.is_whitespace() && !quoted => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            c => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if quoted {
+        return Err("missing closing quote".to_string());
+    }
+    if in_word {
+        words.push(word);
+    }
+    Ok(words)
+}
+
+/// Byte index where the last, possibly unfinished, word of the line starts
+pub fn last_word_start(line: &str) -> usize {
+    let mut start = 0;
+    let mut quoted = false;
+    let mut previous_space = true;
+    for (i, c) in line.char_indices() {
+        if c == '"' {
+            quoted = !quoted;
+        }
+        let space = c.is_whitespace() && !quoted;
+        if previous_space && !space {
+            start = i;
+        }
+        if space {
+            start = i + c.len_utf8();
+        }
+        previous_space = space;
+    }
+    start
+}
+
+/// Quote a word when it contains whitespace
+pub fn quote(word: &str) -> String {
+    if word.chars().any(char::is_whitespace) {
+        format!("\"{word}\"")
+    } else {
+        word.to_string()
+    }
+}
+
+fn parse_day(value: &str) -> Result<Date, String> {
+    if value == "today" {
+        return Ok(today());
+    }
+    parse_display_date(value).ok_or(format!("'{value}' is not a date"))
+}
+
+impl LineCommand {
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let words = split_words(line)?;
+        let words: Vec<&str> = words.iter().map(String::as_str).collect();
+        match words.as_slice() {
+            ["log", date, location] | ["log", date, location, _] => Ok(LineCommand::Log {
+                date: parse_day(date)?,
+                location: location.to_string(),
+                log_type: match words.get(3) {
+                    Some(log_type) => log_type.parse()?,
+                    None => config().default_log_type.clone(),
+                },
+            }),
+            ["log", ..] => Err("usage: log <date> <location> [type]".to_string()),
+            ["goto", date] => parse_month(date)
+                .or_else(|| parse_day(date).ok())
+                .map(LineCommand::Goto)
+                .ok_or(format!("'{date}' is not a date or a month like 2026-01")),
+            ["goto", ..] => Err("usage: goto <date|YYYY-MM>".to_string()),
+            ["loc", "add", name] => Ok(LineCommand::AddLocation {
+                name: name.to_string(),
+                tag: None,
+            }),
+            ["loc", "add", name, tag] => Ok(LineCommand::AddLocation {
+                name: name.to_string(),
+                tag: Some(tag.to_string()),
+            }),
+            ["loc", "remove", name] => Ok(LineCommand::RemoveLocation(name.to_string())),
+            ["loc", "category", name, category] => Ok(LineCommand::SetCategory {
+                name: name.to_string(),
+                category: category.parse()?,
+            }),
+            ["loc", "archive", name] => Ok(LineCommand::ArchiveLocation {
+                name: name.to_string(),
+                archived: true,
+            }),
+            ["loc", "unarchive", name] => Ok(LineCommand::ArchiveLocation {
+                name: name.to_string(),
+                archived: false,
+            }),
+            ["loc", ..] => Err(
+                "usage: loc add <name> [tag] | loc remove|archive|unarchive <name> \
+                    | loc category <name> <category>"
+                    .to_string(),
+            ),
+            ["export", format] | ["export", format, _] => {
+                let format = ExportFormat::from_str(format, true)
+                    .map_err(|_| format!("'{format}' is not one of csv, json, ics"))?;
+                let path = match words.get(2) {
+                    Some(path) => PathBuf::from(path),
+                    None => PathBuf::from(format!("locwork-records.{}", format.extension())),
+                };
+                Ok(LineCommand::Export { format, path })
+            }
+            ["export", ..] => Err("usage: export <csv|json|ics> [path]".to_string()),
+            ["q"] | ["quit"] => Ok(LineCommand::Quit),
+            [] => Err("no command".to_string()),
+            [command, ..] => Err(format!(
+                "unknown command '{command}', expected one of {}",
+                COMMANDS.join(", ")
+            )),
+        }
+    }
+
+    /// Values the word after `words` can take, e.g. the location names after `log <date>`
+    pub fn completions(words: &[String], locations: &[Location]) -> Vec<String> {
+        let words: Vec<&str> = words.iter().map(String::as_str).collect();
+        let names = |archived: Option<bool>| -> Vec<String> {
+            locations
+                .iter()
+                .filter(|l| archived.is_none_or(|a| l.archived == a))
+                .map(|l| l.name.clone())
+                .collect()
+        };
+        let values: Vec<String> = match words.as_slice() {
+            [] => COMMANDS.iter().map(|c| c.to_string()).collect(),
+            ["log"] => vec!["today".to_string()],
+            ["log", _] | ["loc", "archive"] => names(Some(false)),
+            ["loc", "unarchive"] => names(Some(true)),
+            ["loc", "remove"] | ["loc", "category"] => names(None),
+            ["loc", "category", _] => Category::names().iter().map(|n| n.to_lowercase()).collect(),
+            ["log", _, _] => LogType::names().iter().map(|n| n.to_lowercase()).collect(),
+            ["loc"] => ["add", "remove", "category", "archive", "unarchive"]
+                .iter()
+                .map(|c| c.to_string())
+                .collect(),
+            ["export"] => ExportFormat::value_variants()
+                .iter()
+                .map(|f| f.extension().to_string())
+                .collect(),
+            _ => Vec::new(),
+        };
+        values.iter().map(|v| quote(v)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::date;
+
+    use super::*;
+
+    fn words(line: &str) -> Vec<String> {
+        split_words(line).unwrap()
+    }
+
+    fn location(name: &str, archived: bool) -> Location {
+        Location {
+            id: 0,
+            name: name.to_string(),
+            tag: String::new(),
+            archived,
+            category: Category::Unknown,
+        }
+    }
+
+    #[test]
+    fn split_words_keeps_quoted_text_together() {
+        assert_eq!(
+            words(r#"log today "New York"  work"#),
+            ["log", "today", "New York", "work"]
+        );
+        assert_eq!(words(r#"loc add "" x"#), ["loc", "add", "", "x"]);
+        assert_eq!(words(r#"loc add New" "York"#), ["loc", "add", "New York"]);
+        assert_eq!(words("  "), Vec::<String>::new());
+        assert!(split_words(r#"log today "New York"#).is_err());
+    }
+
+    #[test]
+    fn last_word_start_skips_quoted_spaces() {
+        assert_eq!(last_word_start(""), 0);
+        assert_eq!(last_word_start("log"), 0);
+        assert_eq!(last_word_start("log "), 4);
+        assert_eq!(last_word_start(r#"log today "New Yo"#), 10);
+        assert_eq!(last_word_start(r#"log today "New York" "#), 21);
+    }
+
+    #[test]
+    fn parse_commands() {
+        assert_eq!(
+            LineCommand::parse(r#"log 2026-10-07 "New York""#),
+            Ok(LineCommand::Log {
+                date: date!(2026 - 10 - 07),
+                location: "New York".to_string(),
+                log_type: LogType::Work,
+            })
+        );
+        assert_eq!(
+            LineCommand::parse("log 2026-10-07 Home sick"),
+            Ok(LineCommand::Log {
+                date: date!(2026 - 10 - 07),
+                location: "Home".to_string(),
+                log_type: LogType::Sick,
+            })
+        );
+        assert_eq!(
+            LineCommand::parse("goto 2026-03"),
+            Ok(LineCommand::Goto(date!(2026 - 03 - 01)))
+        );
+        assert_eq!(
+            LineCommand::parse("goto 2026-03-02"),
+            Ok(LineCommand::Goto(date!(2026 - 03 - 02)))
+        );
+        assert_eq!(
+            LineCommand::parse("loc add Beach b"),
+            Ok(LineCommand::AddLocation {
+                name: "Beach".to_string(),
+                tag: Some("b".to_string()),
+            })
+        );
+        assert_eq!(
+            LineCommand::parse("export ics"),
+            Ok(LineCommand::Export {
+                format: ExportFormat::Ics,
+                path: PathBuf::from("locwork-records.ics"),
+            })
+        );
+        assert_eq!(LineCommand::parse("q"), Ok(LineCommand::Quit));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(LineCommand::parse("").is_err());
+        assert!(LineCommand::parse("log yesterday Home").is_err());
+        assert!(LineCommand::parse("log today").is_err());
+        assert!(LineCommand::parse("goto someday").is_err());
+        assert!(LineCommand::parse("loc category Home garden").is_err());
+        assert!(LineCommand::parse("export pdf").is_err());
+        assert!(LineCommand::parse("frobnicate").is_err());
+    }
+
+    #[test]
+    fn completions_follow_the_typed_words() {
+        let locations = [
+            location("Home", false),
+            location("New York", false),
+            location("Beach", true),
+        ];
+        let complete = |line: &str| LineCommand::completions(&words(line), &locations);
+        assert_eq!(complete(""), COMMANDS);
+        assert_eq!(complete("log"), ["today"]);
+        assert_eq!(complete("log today"), ["Home", "\"New York\""]);
+        assert_eq!(complete("loc unarchive"), ["Beach"]);
+        assert_eq!(complete("loc remove").len(), 3);
+        assert_eq!(
+            complete("log today Home"),
+            ["unknown", "work", "holiday", "vacation", "sick"]
+        );
+        assert_eq!(complete("export"), ["csv", "json", "ics"]);
+        assert!(complete("quit now").is_empty());
+    }
+}
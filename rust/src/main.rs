@@ -1,29 +1,57 @@
+mod cli;
+mod config;
+mod dates;
+mod export;
+mod holidays;
+mod import;
+mod keymap;
+mod line_command;
 mod panels;
+mod stats;
 mod store;
 use tokio::sync::mpsc::{Sender, channel};
 
-use color_eyre::Result;
-use crossterm::event::KeyEvent;
-use crossterm::event::{self, Event, EventStream};
+use clap::Parser;
+use color_eyre::{Result, eyre::eyre};
+use crossterm::event::{Event, EventStream};
 use futures_util::StreamExt;
 use ratatui::{
     DefaultTerminal,
     layout::{Constraint, Layout, Rect},
+    style::Stylize,
+    text::{Line, Span},
 };
-use std::collections::HashMap;
 use std::sync::Arc;
+use std::{collections::HashMap, fs::File, time::Duration};
+use time::Date;
 
 use crate::{
+    cli::Cli,
+    config::{Config, config, config_dir},
+    dates::{
+        add_days, clamp_navigable, display_date, month_bounds, today, week_start, year_bounds,
+    },
+    export::write_records,
+    holidays::Country,
+    keymap::{Command, Dispatch, KeyContext, KeyDispatcher},
+    line_command::LineCommand,
     panels::{
         Action, PanelType,
-        calendar_panel::CalendarPanel,
-        location_modal::{AddLocationModal, InputModalResult},
+        calendar_panel::{CalendarPanel, CalendarView, HEATMAP_WEEKS},
+        command_line::{CommandLine, CommandLineResult},
+        confirm_modal::ConfirmModal,
+        help_modal::HelpModal,
+        location_modal::{
+            AddLocationModal, EditLocationModal, EditLocationResult, InputModalResult,
+        },
         location_panel::LocationsPanel,
         modal::ActiveModal,
         panel::Panel,
-        record_modal::{AddRecordModal, RecordModalResult},
+        record_modal::{AddRecordModal, RecordModalData, RecordModalResult},
+        status_bar::{Notification, StatusBar},
+        status_panel::StatusPanel,
     },
-    store::Store,
+    store::{Category, Location, Profile, Record, Store},
 };
 
 /// Context of app
@@ -32,69 +60,847 @@ pub struct Context {
     pub rects: HashMap<PanelType, Rect>,
     pub focussed: PanelType,
     pub active_modal: ActiveModal,
+    /// Keys typed so far that don't form a command yet
+    pub keys: KeyDispatcher,
+    /// Shown over the active modal
+    pub help: Option<HelpModal>,
+    pub command_line: CommandLine,
+    pub status_bar: StatusBar,
+    /// Any day of the month shown in the calendar
+    pub month: Date,
+    /// How much of the calendar is shown around the selected day
+    pub view: CalendarView,
+    /// The days whose records the panels hold
+    pub loaded: Option<(Date, Date)>,
+    /// Locations deleted this session with their records, the last one is undone first
+    pub deleted_locations: Vec<(Location, Vec<Record>)>,
+    /// Records deleted this session, the last one is undone first
+    pub deleted_records: Vec<Record>,
+    /// Identifies the database in exported calendar events
+    pub calendar_id: String,
 }
 impl Context {
+    /// Whether the panels hold all records they show around `date`
+    fn records_cover(&self, date: Date) -> bool {
+        self.loaded
+            .is_some_and(|loaded| covers(loaded, records_range(date, self.view)))
+    }
+
+    /// Let every panel in `panel_types` react to an action
+    fn update_panels(&mut self, panel_types: &[PanelType], action: &Action) {
+        for panel_type in panel_types {
+            if let Some(panel) = self.panels.get_mut(panel_type) {
+                panel.update(action);
+            }
+        }
+    }
+
     pub async fn process_action(&mut self, action: Action, tx: Sender<Action>, store: Arc<Store>) {
         match action {
             Action::None => {}
             Action::CancelModal => {
                 self.active_modal = ActiveModal::None;
             }
-            Action::AddLocation(_m) => {
-                self.active_modal = ActiveModal::AddLocation(AddLocationModal::new());
+            Action::ShowHelp(contexts) => {
+                self.help = Some(HelpModal::new(&contexts, &config().keymap));
+            }
+            Action::CloseHelp => {
+                self.help = None;
             }
+            Action::Notify(notification) => self.status_bar.notify(notification),
+            Action::DismissNotifications => self.status_bar.dismiss(),
+            Action::OpenCommandLine(line) => {
+                // the command line still opens, only without completing location names
+                let locations = match store.get_locations().await {
+                    Ok(locations) => locations,
+                    Err(e) => {
+                        self.status_bar.notify(Notification::error(format!(
+                            "could not load the locations: {e}"
+                        )));
+                        Vec::new()
+                    }
+                };
+                self.command_line.open(locations, line);
+            }
+            Action::RunCommand(command) => self.run_command(command, tx, store).await,
+            Action::AddLocation(_m) => match store.get_locations().await {
+                Ok(locations) => {
+                    self.active_modal = ActiveModal::AddLocation(AddLocationModal::new(&locations));
+                }
+                Err(e) => self.status_bar.notify(Notification::error(format!(
+                    "could not load the locations: {e}"
+                ))),
+            },
             Action::DeleteLocation(name) => {
-                let tx_clone = tx.clone();
-                let store_clone = Arc::clone(&store);
+                let location = match store.get_location_by_name(&name).await {
+                    Ok(Some(location)) => location,
+                    Ok(None) => return,
+                    Err(e) => {
+                        self.status_bar.notify(Notification::error(e.to_string()));
+                        return;
+                    }
+                };
+                let count = match store.get_records_for_location(&location).await {
+                    Ok(records) => records.len(),
+                    Err(e) => {
+                        self.status_bar.notify(Notification::error(e.to_string()));
+                        return;
+                    }
+                };
+                let question = match count {
+                    0 => format!("Delete location '{name}'? It has no records."),
+                    1 => format!("Delete location '{name}'? Its record will be lost as well."),
+                    _ => format!(
+                        "Delete location '{name}'? Its {count} records will be lost as well."
+                    ),
+                };
+                let modal = ConfirmModal::new(
+                    "Delete Location".to_string(),
+                    question,
+                    Action::ConfirmDeleteLocation(name),
+                );
+                self.active_modal = ActiveModal::Confirm(modal);
+            }
+            Action::ConfirmDeleteLocation(name) => {
+                self.active_modal = ActiveModal::None;
                 tokio::spawn(async move {
-                    if let Ok(_) = store_clone.delete_location_by_name(name.as_str()).await {
-                        let locations = store_clone.get_locations().await.unwrap();
-                        let _ = tx_clone
-                            .send(Action::DeleteLocationDbSuccess(locations))
+                    // keep the location and its records around to undo the deletion
+                    let backup = match store.get_location_by_name(&name).await {
+                        Ok(Some(location)) => store
+                            .get_records_for_location(&location)
+                            .await
+                            .map(|records| (location, records)),
+                        Ok(None) => return,
+                        Err(e) => Err(e),
+                    };
+                    let (location, records) = match backup {
+                        Ok(backup) => backup,
+                        Err(e) => {
+                            notify(
+                                &tx,
+                                Notification::error(format!(
+                                    "could not remove location '{name}': {e}"
+                                )),
+                            )
+                            .await;
+                            return;
+                        }
+                    };
+                    if let Err(e) = store.delete_location_by_name(name.as_str()).await {
+                        notify(
+                            &tx,
+                            Notification::error(format!("could not remove location '{name}': {e}")),
+                        )
+                        .await;
+                        return;
+                    }
+                    let count = records.len();
+                    let _ = tx.send(Action::LocationDeleted(location, records)).await;
+                    match store.get_locations().await {
+                        Ok(locations) => {
+                            let _ = tx.send(Action::DeleteLocationDbSuccess(locations)).await;
+                            let _ = tx.send(Action::ReloadRecords).await;
+                            let undo = config()
+                                .keymap
+                                .key_for(KeyContext::Locations, Command::Undo)
+                                .map(|key| format!(", {key} to undo"))
+                                .unwrap_or_default();
+                            notify(
+                                &tx,
+                                Notification::success(format!(
+                                    "removed location {name} and {}{undo}",
+                                    record_count(count as u64)
+                                )),
+                            )
                             .await;
+                        }
+                        Err(e) => {
+                            notify(
+                                &tx,
+                                Notification::error(format!("could not load the locations: {e}")),
+                            )
+                            .await
+                        }
                     }
                 });
             }
-            Action::AddLocationDbSuccess(ref _locations) => {
-                if let Some(panel) = self.panels.get_mut(&PanelType::Locations) {
-                    panel.update(&action);
+            Action::LocationDeleted(location, records) => {
+                self.deleted_locations.push((location, records));
+            }
+            Action::UndoDeleteLocation => {
+                let Some((location, records)) = self.deleted_locations.pop() else {
+                    self.status_bar
+                        .notify(Notification::warning("nothing to undo"));
+                    return;
+                };
+                match store.get_location_by_name(&location.name).await {
+                    Ok(Some(_)) => {
+                        self.status_bar.notify(Notification::error(format!(
+                            "can't undo, location '{}' has been added again",
+                            location.name
+                        )));
+                        return;
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        self.status_bar.notify(Notification::error(format!(
+                            "could not restore location '{}': {e}",
+                            location.name
+                        )));
+                        // kept to try again
+                        self.deleted_locations.push((location, records));
+                        return;
+                    }
                 }
+                tokio::spawn(async move {
+                    match store.restore_location(&location, &records).await {
+                        Ok((restored, count)) => {
+                            match store.get_locations().await {
+                                Ok(locations) => {
+                                    let _ = tx.send(Action::AddLocationDbSuccess(locations)).await;
+                                }
+                                Err(e) => {
+                                    notify(
+                                        &tx,
+                                        Notification::error(format!(
+                                            "could not load the locations: {e}"
+                                        )),
+                                    )
+                                    .await
+                                }
+                            }
+                            let _ = tx.send(Action::ReloadRecords).await;
+                            let skipped = records.len() as u64 - count;
+                            let message = format!(
+                                "restored location {} and {}",
+                                restored.name,
+                                record_count(count)
+                            );
+                            let notification = if skipped > 0 {
+                                Notification::warning(format!(
+                                    "{message}, {skipped} days were logged again in the meantime"
+                                ))
+                            } else {
+                                Notification::success(message)
+                            };
+                            notify(&tx, notification).await;
+                        }
+                        Err(e) => {
+                            notify(
+                                &tx,
+                                Notification::error(format!(
+                                    "could not restore location '{}': {e}",
+                                    location.name
+                                )),
+                            )
+                            .await
+                        }
+                    }
+                });
+            }
+            Action::AddLocationDbSuccess(ref _locations) => {
+                self.update_panels(
+                    &[PanelType::Locations, PanelType::Status, PanelType::Calendar],
+                    &action,
+                );
             }
             Action::ConfirmAddLocation(name) => {
                 self.active_modal = ActiveModal::None;
-                let tx_clone = tx.clone();
-                let store_clone = Arc::clone(&store);
                 tokio::spawn(async move {
-                    if let Ok(_) = store_clone.add_location(name, None).await {
-                        let locations = store_clone.get_locations().await.unwrap();
-                        let _ = tx_clone.send(Action::AddLocationDbSuccess(locations)).await;
+                    match store.get_location_by_name(&name).await {
+                        Ok(Some(_)) => {
+                            notify(
+                                &tx,
+                                Notification::warning(format!("location '{name}' already exists")),
+                            )
+                            .await;
+                            return;
+                        }
+                        Ok(None) => {}
+                        Err(e) => {
+                            notify(
+                                &tx,
+                                Notification::error(format!(
+                                    "could not add location '{name}': {e}"
+                                )),
+                            )
+                            .await;
+                            return;
+                        }
+                    }
+                    if let Err(e) = store
+                        .add_location(name.clone(), None, Category::Unknown)
+                        .await
+                    {
+                        notify(
+                            &tx,
+                            Notification::error(format!("could not add location '{name}': {e}")),
+                        )
+                        .await;
+                        return;
+                    }
+                    match store.get_locations().await {
+                        Ok(locations) => {
+                            let _ = tx.send(Action::AddLocationDbSuccess(locations)).await;
+                            notify(&tx, Notification::success(format!("added location {name}")))
+                                .await;
+                        }
+                        Err(e) => {
+                            notify(
+                                &tx,
+                                Notification::error(format!("could not load the locations: {e}")),
+                            )
+                            .await
+                        }
                     }
                 });
             }
-            Action::DeleteLocationDbSuccess(ref _locations) => {
+            Action::DeleteLocationDbSuccess(ref _locations)
+            | Action::ArchiveLocationDbSuccess(ref _locations)
+            | Action::EditLocationDbSuccess(ref _locations) => {
                 // TODO: merge with AddLocationDBSuccess -> LocationDbUpdated
-                if let Some(panel) = self.panels.get_mut(&PanelType::Locations) {
-                    panel.update(&action);
+                self.update_panels(
+                    &[PanelType::Locations, PanelType::Status, PanelType::Calendar],
+                    &action,
+                );
+            }
+            Action::EditLocation(name) => match store.get_locations().await {
+                Ok(locations) => {
+                    if let Some(location) = locations.iter().find(|l| l.name == name) {
+                        let modal = EditLocationModal::new(location.clone(), &locations);
+                        self.active_modal = ActiveModal::EditLocation(modal);
+                    }
+                }
+                Err(e) => self.status_bar.notify(Notification::error(format!(
+                    "could not load the locations: {e}"
+                ))),
+            },
+            Action::ConfirmEditLocation(location) => {
+                self.active_modal = ActiveModal::None;
+                tokio::spawn(async move {
+                    if let Err(e) = store.update_location(&location).await {
+                        notify(
+                            &tx,
+                            Notification::error(format!("could not save the location: {e}")),
+                        )
+                        .await;
+                        return;
+                    }
+                    match store.get_locations().await {
+                        Ok(locations) => {
+                            let _ = tx.send(Action::EditLocationDbSuccess(locations)).await;
+                            notify(
+                                &tx,
+                                Notification::success(format!("saved location {}", location.name)),
+                            )
+                            .await;
+                        }
+                        Err(e) => {
+                            notify(
+                                &tx,
+                                Notification::error(format!("could not load the locations: {e}")),
+                            )
+                            .await
+                        }
+                    }
+                });
+            }
+            Action::ArchiveLocation(name, archived) => {
+                tokio::spawn(async move {
+                    let (result, done) = if archived {
+                        (store.archive_location(&name).await, "archived")
+                    } else {
+                        (store.unarchive_location(&name).await, "unarchived")
+                    };
+                    if let Err(e) = result {
+                        notify(&tx, Notification::error(e.to_string())).await;
+                        return;
+                    }
+                    match store.get_locations().await {
+                        Ok(locations) => {
+                            let _ = tx.send(Action::ArchiveLocationDbSuccess(locations)).await;
+                            notify(
+                                &tx,
+                                Notification::success(format!("{done} location {name}")),
+                            )
+                            .await;
+                        }
+                        Err(e) => {
+                            notify(
+                                &tx,
+                                Notification::error(format!("could not load the locations: {e}")),
+                            )
+                            .await
+                        }
+                    }
+                });
+            }
+            Action::StartNavigateDate(date) => {
+                // a date typed on the command line can lie outside the calendar
+                let date = clamp_navigable(date);
+                self.month = date;
+                self.update_panels(
+                    &[PanelType::Calendar, PanelType::Status],
+                    &Action::StartNavigateDate(date),
+                );
+                if !self.records_cover(date) {
+                    spawn_load_records(date, self.view, tx, store);
+                }
+            }
+            Action::ChangeCalendarView(view) => {
+                self.view = view;
+                if !self.records_cover(self.month) {
+                    spawn_load_records(self.month, view, tx, store);
+                }
+            }
+            Action::ReloadRecords => {
+                spawn_load_records(self.month, self.view, tx, store);
+            }
+            Action::LoadRecordsSuccess(range, ref _records) => {
+                // a slow load can finish after the calendar moved on
+                if !covers(range, records_range(self.month, self.view)) {
+                    return;
                 }
+                self.loaded = Some(range);
+                self.update_panels(&[PanelType::Calendar, PanelType::Status], &action);
             }
-            Action::StartNavigateDate(_date) => {}
-            Action::LoadNavigateDateSuccess(ref _dates) => {
-                // TODO: Update Calendar, load new dates
-                if let Some(panel) = self.panels.get_mut(&PanelType::Calendar) {
-                    panel.update(&action);
+            Action::AddRecord(data) => match store.get_locations().await {
+                Ok(locations) => {
+                    let new_modal = AddRecordModal::new(data, locations);
+                    self.active_modal = ActiveModal::AddRecord(new_modal);
                 }
-                // TODO: Update statistics Panel
+                Err(e) => self.status_bar.notify(Notification::error(format!(
+                    "could not load the locations: {e}"
+                ))),
+            },
+            Action::ConfirmAddRecord(data) => {
+                let mut editing = false;
+                if let ActiveModal::AddRecord(modal) = &mut self.active_modal {
+                    editing = modal.edits(data.date);
+                    match store.get_record_by_date(data.date).await {
+                        Ok(Some(existing)) if !modal.edits(existing.date) => {
+                            modal.ask_replace(&existing);
+                            return;
+                        }
+                        Ok(_) => {}
+                        Err(e) => {
+                            self.status_bar.notify(Notification::error(format!(
+                                "could not check the day: {e}"
+                            )));
+                            return;
+                        }
+                    }
+                }
+                self.active_modal = ActiveModal::None;
+                spawn_save_record(data, editing, tx, store);
+            }
+            Action::ReplaceRecord(data) => {
+                self.active_modal = ActiveModal::None;
+                spawn_save_record(data, false, tx, store);
             }
-            Action::AddRecord(_data) => {
-                let locations = store.get_locations().await.unwrap();
-                let new_modal = AddRecordModal::new(_data.date, locations);
-                self.active_modal = ActiveModal::AddRecord(new_modal);
+            Action::AddRecordDbSuccess(ref _record) => {
+                self.update_panels(&[PanelType::Calendar, PanelType::Status], &action);
+            }
+            Action::DeleteRecord(date) => {
+                let record = match store.get_record_by_date(date).await {
+                    Ok(Some(record)) => record,
+                    Ok(None) => return,
+                    Err(e) => {
+                        self.status_bar.notify(Notification::error(e.to_string()));
+                        return;
+                    }
+                };
+                let location = match store.get_locations().await {
+                    Ok(locations) => locations
+                        .into_iter()
+                        .find(|l| l.id == record.location_id)
+                        .map(|l| l.name)
+                        .unwrap_or_default(),
+                    Err(e) => {
+                        self.status_bar.notify(Notification::error(e.to_string()));
+                        return;
+                    }
+                };
+                let modal = ConfirmModal::new(
+                    "Delete Record".to_string(),
+                    format!("Delete the record of {} at {location}?", display_date(date)),
+                    Action::ConfirmDeleteRecord(date),
+                );
+                self.active_modal = ActiveModal::Confirm(modal);
+            }
+            Action::ConfirmDeleteRecord(date) => {
+                self.active_modal = ActiveModal::None;
+                tokio::spawn(async move {
+                    // keep the record around to undo the deletion
+                    let record = match store.get_record_by_date(date).await {
+                        Ok(Some(record)) => record,
+                        Ok(None) => return,
+                        Err(e) => {
+                            notify(
+                                &tx,
+                                Notification::error(format!("could not delete the record: {e}")),
+                            )
+                            .await;
+                            return;
+                        }
+                    };
+                    match store.delete_record_by_date(date).await {
+                        Ok(()) => {
+                            let _ = tx.send(Action::RecordDeleted(record)).await;
+                            let _ = tx.send(Action::DeleteRecordDbSuccess(date)).await;
+                            let undo = config()
+                                .keymap
+                                .key_for(KeyContext::Calendar, Command::Undo)
+                                .map(|key| format!(", {key} to undo"))
+                                .unwrap_or_default();
+                            notify(
+                                &tx,
+                                Notification::success(format!(
+                                    "deleted the record of {}{undo}",
+                                    display_date(date)
+                                )),
+                            )
+                            .await;
+                        }
+                        Err(e) => {
+                            notify(
+                                &tx,
+                                Notification::error(format!("could not delete the record: {e}")),
+                            )
+                            .await
+                        }
+                    }
+                });
+            }
+            Action::RecordDeleted(record) => {
+                self.deleted_records.push(record);
+            }
+            Action::UndoDeleteRecord => {
+                let Some(record) = self.deleted_records.pop() else {
+                    self.status_bar
+                        .notify(Notification::warning("nothing to undo"));
+                    return;
+                };
+                let date = display_date(record.date);
+                match store.get_record_by_date(record.date).await {
+                    Ok(Some(_)) => {
+                        self.status_bar.notify(Notification::error(format!(
+                            "can't undo, {date} has been logged again"
+                        )));
+                        return;
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        self.status_bar.notify(Notification::error(format!(
+                            "could not restore the record: {e}"
+                        )));
+                        // kept to try again
+                        self.deleted_records.push(record);
+                        return;
+                    }
+                }
+                let location = match store.get_locations().await {
+                    Ok(locations) => locations.into_iter().find(|l| l.id == record.location_id),
+                    Err(e) => {
+                        self.status_bar.notify(Notification::error(e.to_string()));
+                        return;
+                    }
+                };
+                let Some(location) = location else {
+                    self.status_bar.notify(Notification::error(format!(
+                        "can't undo, the location of {date} has been deleted"
+                    )));
+                    return;
+                };
+                tokio::spawn(async move {
+                    match store
+                        .add_record(record.date, record.log_type, location, record.note)
+                        .await
+                    {
+                        Ok(restored) => {
+                            let _ = tx.send(Action::AddRecordDbSuccess(restored)).await;
+                            notify(
+                                &tx,
+                                Notification::success(format!("restored the record of {date}")),
+                            )
+                            .await;
+                        }
+                        Err(e) => {
+                            notify(
+                                &tx,
+                                Notification::error(format!("could not restore the record: {e}")),
+                            )
+                            .await
+                        }
+                    }
+                });
+            }
+            Action::DeleteRecordDbSuccess(_date) => {
+                self.update_panels(&[PanelType::Calendar, PanelType::Status], &action);
             }
             Action::Skipped => {}
             Action::Processing => {}
             Action::QuitApplication => panic!(),
         }
     }
+
+    /// Run a command typed on the command line, its result is shown in the status bar
+    async fn run_command(&mut self, command: LineCommand, tx: Sender<Action>, store: Arc<Store>) {
+        match command {
+            LineCommand::Log {
+                date,
+                location,
+                log_type,
+            } => match store.get_location_by_name(&location).await {
+                Ok(Some(found)) if found.archived => self.status_bar.notify(Notification::error(
+                    format!("location '{location}' is archived"),
+                )),
+                Ok(Some(_)) => {
+                    let data = RecordModalData {
+                        date,
+                        location,
+                        log_type,
+                        note: None,
+                    };
+                    spawn_save_record(data, false, tx, store);
+                }
+                Ok(None) => self.status_bar.notify(Notification::error(format!(
+                    "location '{location}' is not recognized"
+                ))),
+                Err(e) => self.status_bar.notify(Notification::error(e.to_string())),
+            },
+            LineCommand::Goto(date) => {
+                Box::pin(self.process_action(Action::StartNavigateDate(date), tx, store)).await;
+            }
+            LineCommand::AddLocation { name, tag } => {
+                match store.get_location_by_name(&name).await {
+                    Ok(Some(_)) => {
+                        self.status_bar.notify(Notification::warning(format!(
+                            "location '{name}' already exists"
+                        )));
+                        return;
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        self.status_bar.notify(Notification::error(format!(
+                            "could not add location '{name}': {e}"
+                        )));
+                        return;
+                    }
+                }
+                match store
+                    .add_location(name.clone(), tag, Category::Unknown)
+                    .await
+                {
+                    Ok(_) => {
+                        self.status_bar
+                            .notify(Notification::success(format!("added location {name}")));
+                        match store.get_locations().await {
+                            Ok(locations) => {
+                                let action = Action::AddLocationDbSuccess(locations);
+                                self.update_panels(
+                                    &[PanelType::Locations, PanelType::Status, PanelType::Calendar],
+                                    &action,
+                                );
+                            }
+                            Err(e) => self.status_bar.notify(Notification::error(format!(
+                                "could not load the locations: {e}"
+                            ))),
+                        }
+                    }
+                    Err(e) => self.status_bar.notify(Notification::error(format!(
+                        "could not add location '{name}': {e}"
+                    ))),
+                }
+            }
+            LineCommand::RemoveLocation(name) => match store.get_location_by_name(&name).await {
+                Ok(Some(_)) => {
+                    Box::pin(self.process_action(Action::DeleteLocation(name), tx, store)).await;
+                }
+                Ok(None) => self.status_bar.notify(Notification::error(format!(
+                    "location '{name}' is not recognized"
+                ))),
+                Err(e) => self.status_bar.notify(Notification::error(e.to_string())),
+            },
+            LineCommand::SetCategory { name, category } => {
+                match store.get_location_by_name(&name).await {
+                    Ok(Some(location)) => {
+                        let action = Action::ConfirmEditLocation(Location {
+                            category,
+                            ..location
+                        });
+                        Box::pin(self.process_action(action, tx, store)).await;
+                    }
+                    Ok(None) => self.status_bar.notify(Notification::error(format!(
+                        "location '{name}' is not recognized"
+                    ))),
+                    Err(e) => self.status_bar.notify(Notification::error(e.to_string())),
+                }
+            }
+            LineCommand::ArchiveLocation { name, archived } => {
+                Box::pin(self.process_action(Action::ArchiveLocation(name, archived), tx, store))
+                    .await;
+            }
+            LineCommand::Export { format, path } => {
+                let result = match store.get_record_details(None, None, None).await {
+                    Ok(records) => File::create(&path)
+                        .map_err(|e| eyre!("could not create {}: {e}", path.display()))
+                        .and_then(|file| write_records(&records, format, &self.calendar_id, file))
+                        .map(|_| records.len()),
+                    Err(e) => Err(e),
+                };
+                let notification = match result {
+                    Ok(count) => Notification::success(format!(
+                        "exported {count} records to {}",
+                        path.display()
+                    )),
+                    Err(e) => Notification::error(e.to_string()),
+                };
+                self.status_bar.notify(notification);
+            }
+            // handled while reading the key, the loop has to stop
+            LineCommand::Quit => {}
+        }
+    }
+}
+
+fn record_modal_action(result: RecordModalResult) -> Action {
+    match result {
+        RecordModalResult::Confirmed(data) => Action::ConfirmAddRecord(data),
+        RecordModalResult::ConfirmedReplace(data) => Action::ReplaceRecord(data),
+        RecordModalResult::Cancelled => Action::CancelModal,
+        RecordModalResult::Invalid(message) => Action::Notify(Notification::error(message)),
+    }
+}
+
+/// `1 record` or `n records`
+fn record_count(count: u64) -> String {
+    match count {
+        1 => "1 record".to_string(),
+        _ => format!("{count} records"),
+    }
+}
+
+/// Post a message to the status bar from a background task
+async fn notify(tx: &Sender<Action>, notification: Notification) {
+    let _ = tx.send(Action::Notify(notification)).await;
+}
+
+/// The days the calendar and the statistics show records of while `date` is selected in
+/// `view`. The year and the heatmap load whole years, the heatmap can reach back into the
+/// previous year and up to today.
+fn records_range(date: Date, view: CalendarView) -> (Date, Date) {
+    let (first, last) = month_bounds(date);
+    match view {
+        CalendarView::Month => (first, last),
+        CalendarView::Week => {
+            let week_first = week_start(date, config().first_weekday);
+            (first.min(week_first), last.max(add_days(week_first, 6)))
+        }
+        CalendarView::Year | CalendarView::Heatmap => {
+            let (first, last) = year_bounds(date);
+            let first = first.min(add_days(date, -7 * (HEATMAP_WEEKS + 1)));
+            let last = last.max(today());
+            (year_bounds(first).0, year_bounds(last).1)
+        }
+    }
+}
+
+/// Whether the days of `outer` include those of `inner`
+fn covers(outer: (Date, Date), inner: (Date, Date)) -> bool {
+    outer.0 <= inner.0 && inner.1 <= outer.1
+}
+
+/// Load the records shown around `date` in `view` in the background
+fn spawn_load_records(date: Date, view: CalendarView, tx: Sender<Action>, store: Arc<Store>) {
+    tokio::spawn(async move {
+        let range = records_range(date, view);
+        let loaded = match view {
+            CalendarView::Month => store.get_records_in_month(date).await,
+            _ => store.get_records_between(range.0, range.1).await,
+        };
+        match loaded {
+            Ok(records) => {
+                let _ = tx.send(Action::LoadRecordsSuccess(range, records)).await;
+            }
+            Err(e) => {
+                notify(
+                    &tx,
+                    Notification::error(format!("could not load the records: {e}")),
+                )
+                .await
+            }
+        }
+    });
+}
+
+/// Write a record in the background, overwriting the one already logged that day.
+///
+/// When `editing` the record the modal was opened with is saved, so overwriting it is not
+/// warned about.
+fn spawn_save_record(data: RecordModalData, editing: bool, tx: Sender<Action>, store: Arc<Store>) {
+    tokio::spawn(async move {
+        let location = match store.get_location_by_name(&data.location).await {
+            Ok(Some(location)) => location,
+            Ok(None) => {
+                let message = format!("location '{}' is not recognized", data.location);
+                notify(&tx, Notification::error(message)).await;
+                return;
+            }
+            Err(e) => {
+                notify(
+                    &tx,
+                    Notification::error(format!("could not save the record: {e}")),
+                )
+                .await;
+                return;
+            }
+        };
+        let replaced = match store.get_record_by_date(data.date).await {
+            Ok(existing) => existing.is_some(),
+            Err(e) => {
+                notify(
+                    &tx,
+                    Notification::error(format!("could not save the record: {e}")),
+                )
+                .await;
+                return;
+            }
+        };
+        let saved = if replaced {
+            store
+                .upsert_record(data.date, data.log_type, location, data.note)
+                .await
+        } else {
+            store
+                .add_record(data.date, data.log_type, location, data.note)
+                .await
+        };
+        match saved {
+            Ok(record) => {
+                let _ = tx.send(Action::AddRecordDbSuccess(record)).await;
+                let message = format!("logged {} at {}", display_date(data.date), data.location);
+                let notification = if editing {
+                    Notification::success(format!(
+                        "updated the record of {}",
+                        display_date(data.date)
+                    ))
+                } else if replaced {
+                    Notification::warning(format!("{message}, replacing the earlier record"))
+                } else {
+                    Notification::success(message)
+                };
+                notify(&tx, notification).await;
+            }
+            Err(e) => {
+                notify(
+                    &tx,
+                    Notification::error(format!("could not save the record: {e}")),
+                )
+                .await
+            }
+        }
+    });
 }
 
 pub enum Pane {
@@ -103,29 +909,74 @@ pub enum Pane {
     Calendar,
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
+fn main() -> Result<()> {
     color_eyre::install()?;
+    // needs to happen while there is only one thread
+    config::init_local_offset();
+    tokio::runtime::Runtime::new()?.block_on(async_main())
+}
+
+async fn async_main() -> Result<()> {
+    let cli = Cli::parse();
+    match Config::load(cli.config.as_deref()) {
+        Ok(loaded) => config::init(loaded),
+        Err(error) => {
+            eprintln!("error: {error}");
+            std::process::exit(1);
+        }
+    }
+    let profile = Profile::resolve(cli.db, cli.profile.as_deref());
+    if let Some(command) = cli.command {
+        let result = match profile {
+            Ok(profile) => cli::run(command, &profile).await,
+            Err(error) => Err(error),
+        };
+        if let Err(error) = result {
+            eprintln!("error: {error}");
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+    let profile = profile?;
     let terminal = ratatui::init();
-    let result = run(terminal).await;
+    let result = run(terminal, profile, cli.country).await;
     ratatui::restore();
     result
 }
-async fn run(mut terminal: DefaultTerminal) -> Result<()> {
+async fn run(
+    mut terminal: DefaultTerminal,
+    profile: Profile,
+    country: Option<Country>,
+) -> Result<()> {
     // init context and panels
     let mut state = Context {
         panels: HashMap::new(),
         rects: HashMap::new(),
         focussed: PanelType::Calendar,
         active_modal: ActiveModal::None,
+        keys: KeyDispatcher::default(),
+        help: None,
+        command_line: CommandLine::new(config_dir().join("history")),
+        status_bar: StatusBar::default(),
+        month: today(),
+        view: CalendarView::default(),
+        loaded: None,
+        deleted_locations: Vec::new(),
+        deleted_records: Vec::new(),
+        calendar_id: profile.calendar_id(),
     };
-    let store = Arc::new(Store::new().await?);
+    let store = Arc::new(Store::new(&profile).await?);
 
     let (tsender, mut treceiver) = channel::<Action>(128);
     let mut reader = EventStream::new();
-    let calendar_panel = CalendarPanel::new(None).await;
-    let locations = &store.get_locations().await.unwrap();
+    // redraws the screen so toasts disappear without a key press
+    let mut tick = tokio::time::interval(Duration::from_millis(500));
+    let locations = &store.get_locations().await?;
+    let calendar_panel =
+        CalendarPanel::new(None, locations.clone(), country.or(config().country)).await;
+    let start_date = calendar_panel.selected;
     let location_panel = LocationsPanel::new(locations.clone()).await;
+    let status_panel = StatusPanel::new(locations.clone()).await;
 
     state
         .panels
@@ -133,22 +984,61 @@ async fn run(mut terminal: DefaultTerminal) -> Result<()> {
     state
         .panels
         .insert(PanelType::Locations, Box::new(location_panel));
+    state
+        .panels
+        .insert(PanelType::Status, Box::new(status_panel));
+    state
+        .process_action(
+            Action::StartNavigateDate(start_date),
+            tsender.clone(),
+            store.clone(),
+        )
+        .await;
 
     loop {
         terminal.draw(|frame| {
             // layout
+            let [title_rect, body_rect, command_rect] = Layout::vertical([
+                Constraint::Length(1),
+                Constraint::Fill(1),
+                Constraint::Length(1),
+            ])
+            .areas(frame.area());
             let row_constraint = (0..2).map(|_| Constraint::Fill(1));
             let horizontal = Layout::vertical(row_constraint).spacing(1);
-            let rects = horizontal.split(frame.area());
+            let rects = horizontal.split(body_rect);
             let first_rect = rects.first().unwrap().to_owned();
             let last_rect = rects.last().unwrap().to_owned();
+            let top_rects = Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)])
+                .spacing(1)
+                .split(first_rect);
 
             // let mut pane_rects: HashMap<PanelType, Rect> = HashMap::new();
-            state.rects.insert(PanelType::Locations, first_rect);
+            state.rects.insert(PanelType::Locations, top_rects[0]);
+            state.rects.insert(PanelType::Status, top_rects[1]);
             state.rects.insert(PanelType::Calendar, last_rect);
+            // a maximized panel hides the others while it is focussed
+            let maximized = state.focussed == PanelType::Calendar
+                && state
+                    .panels
+                    .get(&PanelType::Calendar)
+                    .is_some_and(|panel| panel.maximized());
+            if maximized {
+                state.rects.insert(PanelType::Calendar, body_rect);
+            }
 
             // draw
-            let visible_panes = vec![PanelType::Locations, PanelType::Calendar];
+            let title = Line::from(vec![
+                Span::raw("locwork").bold(),
+                Span::raw(" · profile: "),
+                Span::raw(profile.name.as_str()).fg(config().focus_color),
+            ]);
+            frame.render_widget(title, title_rect);
+            let visible_panes = if maximized {
+                vec![PanelType::Calendar]
+            } else {
+                vec![PanelType::Locations, PanelType::Status, PanelType::Calendar]
+            };
             for pane_type in &visible_panes {
                 if let (Some(panel), Some(rect)) =
                     (state.panels.get_mut(pane_type), state.rects.get(pane_type))
@@ -162,7 +1052,20 @@ async fn run(mut terminal: DefaultTerminal) -> Result<()> {
             match &mut state.active_modal {
                 ActiveModal::None => {}
                 ActiveModal::AddLocation(modal) => modal.render(frame, active_rect),
+                ActiveModal::EditLocation(modal) => modal.render(frame, active_rect),
                 ActiveModal::AddRecord(modal) => modal.render(frame, active_rect),
+                ActiveModal::Confirm(modal) => modal.render(frame, active_rect),
+            }
+            if let Some(help) = &state.help {
+                help.render(frame, active_rect);
+            }
+            state.status_bar.render_toasts(frame, body_rect);
+            if state.command_line.active {
+                state.command_line.render(frame, command_rect);
+            } else {
+                state
+                    .status_bar
+                    .render(frame, command_rect, &state.keys.pending());
             }
         })?;
 
@@ -172,43 +1075,77 @@ async fn run(mut terminal: DefaultTerminal) -> Result<()> {
                 maybe_event = reader.next() => {
                     if let Some(Ok(Event::Key(key))) = maybe_event {
                         // 1. Determine Intent (Action)
-                        let action: Option<Action> = match &mut state.active_modal {
-                            ActiveModal::AddLocation(_m) => {
-                                if let Some(res) = _m.handle_input(key)      {
-                                    let a: Option<Action> = match res{
-                                        InputModalResult::Editting => {None}
-                                        InputModalResult::Cancelled => {Some(Action::CancelModal)}
-                                        InputModalResult::Confirmed(t) => {Some(Action::ConfirmAddLocation(t))}
-                                    };
-                                a
-                                }
-                                else{
-                                    None
-                                }
-                            },
-                            ActiveModal::AddRecord(_m) =>{
-                                if let Some(res) = _m.handle_input(key) {
-                                    let a: Option<Action> = match res{
-                                        RecordModalResult::Confirmed(_data) => {todo!()}
-                                        RecordModalResult::Cancelled => {Some(Action::CancelModal)}
-                                    };
-                                a
-                                }
-                                else{
-                                    None
-
-                                }
-                            },
-                            ActiveModal::None => {
-                                // Global keys (like quit)
-                                match key.code {
-                                    event::KeyCode::Char('q') => break Ok(()),
-                                    event::KeyCode::Char('1') => { state.focussed = PanelType::Locations; None },
-                                    event::KeyCode::Char('2') => { state.focussed = PanelType::Calendar; None },
-                                    _ => {
-                                        // Pass to panel
-                                        state.panels.get_mut(&state.focussed)
-                                            .and_then(|panel| panel.handle_input(key))
+                        let action: Option<Action> = if let Some(help) = &mut state.help {
+                            match state.keys.feed(key, &config().keymap, &[KeyContext::Help]) {
+                                Dispatch::Matched(command, count) if !help.handle_command(command, count) => Some(Action::CloseHelp),
+                                _ => None,
+                            }
+                        } else if state.command_line.active {
+                            match state.command_line.handle_input(key) {
+                                CommandLineResult::Confirmed(line) => match LineCommand::parse(&line) {
+                                    Ok(LineCommand::Quit) => break Ok(()),
+                                    Ok(command) => Some(Action::RunCommand(command)),
+                                    Err(error) => Some(Action::Notify(Notification::error(error))),
+                                },
+                                CommandLineResult::Editting | CommandLineResult::Cancelled => None,
+                            }
+                        } else {
+                            match &mut state.active_modal {
+                                ActiveModal::AddLocation(modal) => match state.keys.feed(key, &config().keymap, &[KeyContext::LocationModal]) {
+                                    Dispatch::Matched(Command::Help, _) => Some(Action::ShowHelp(vec![KeyContext::LocationModal])),
+                                    Dispatch::Matched(command, _) => match modal.handle_command(command) {
+                                        Some(InputModalResult::Cancelled) => Some(Action::CancelModal),
+                                        Some(InputModalResult::Confirmed(name)) => Some(Action::ConfirmAddLocation(name)),
+                                        None => None,
+                                    },
+                                    Dispatch::Pending => None,
+                                    // typing the name
+                                    Dispatch::Unmatched => { modal.handle_input(key); None },
+                                },
+                                ActiveModal::EditLocation(modal) => match state.keys.feed(key, &config().keymap, &[KeyContext::LocationModal]) {
+                                    Dispatch::Matched(Command::Help, _) => Some(Action::ShowHelp(vec![KeyContext::LocationModal])),
+                                    Dispatch::Matched(command, _) => match modal.handle_command(command) {
+                                        Some(EditLocationResult::Cancelled) => Some(Action::CancelModal),
+                                        Some(EditLocationResult::Confirmed(location)) => Some(Action::ConfirmEditLocation(location)),
+                                        None => None,
+                                    },
+                                    Dispatch::Pending => None,
+                                    // typing into a field
+                                    Dispatch::Unmatched => { modal.handle_input(key); None },
+                                },
+                                ActiveModal::AddRecord(_m) => match _m.key_context() {
+                                    Some(context) => match state.keys.feed(key, &config().keymap, &[context]) {
+                                        Dispatch::Matched(Command::Help, _) => Some(Action::ShowHelp(vec![context])),
+                                        Dispatch::Matched(command, count) => _m.handle_command(command, count).map(record_modal_action),
+                                        Dispatch::Pending | Dispatch::Unmatched => None,
+                                    },
+                                    // typing a date
+                                    None => _m.handle_input(key).map(record_modal_action),
+                                },
+                                ActiveModal::Confirm(modal) => match state.keys.feed(key, &config().keymap, &[KeyContext::Prompt]) {
+                                    Dispatch::Matched(Command::Help, _) => Some(Action::ShowHelp(vec![KeyContext::Prompt])),
+                                    Dispatch::Matched(command, _) => modal.handle_command(command),
+                                    Dispatch::Pending | Dispatch::Unmatched => None,
+                                },
+                                ActiveModal::None => {
+                                    // The focussed panel's bindings win over the global ones
+                                    let context = state.panels.get(&state.focussed)
+                                        .map(|panel| panel.key_context())
+                                        .unwrap_or(KeyContext::Global);
+                                    match state.keys.feed(key, &config().keymap, &context.lookup()) {
+                                        Dispatch::Matched(Command::Quit, _) => break Ok(()),
+                                        Dispatch::Matched(Command::FocusLocations, _) => { state.focussed = PanelType::Locations; None },
+                                        Dispatch::Matched(Command::FocusCalendar, _) => { state.focussed = PanelType::Calendar; None },
+                                        Dispatch::Matched(Command::FocusStatus, _) => { state.focussed = PanelType::Status; None },
+                                        Dispatch::Matched(Command::Help, _) => Some(Action::ShowHelp(context.lookup())),
+                                        Dispatch::Matched(Command::OpenCommandLine, _) => Some(Action::OpenCommandLine(String::new())),
+                                        Dispatch::Matched(Command::Dismiss, _) => Some(Action::DismissNotifications),
+                                        Dispatch::Matched(command, count) => {
+                                            // Pass to panel
+                                            state.panels.get_mut(&state.focussed)
+                                                .and_then(|panel| panel.handle_command(command, count))
+                                        }
+                                        Dispatch::Pending | Dispatch::Unmatched => None,
                                     }
                                 }
                             }
@@ -220,6 +1157,7 @@ async fn run(mut terminal: DefaultTerminal) -> Result<()> {
                         }
                     }
                 }
+                _ = tick.tick() => {}
                 maybe_bg_action = treceiver.recv() => {
                     if let Some(bg_action) = maybe_bg_action {
             // Process the refresh/success action sent from the tokio::spawn block
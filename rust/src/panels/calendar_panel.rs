@@ -1,86 +1,586 @@
-use crossterm::event;
+use std::collections::HashMap;
+
 use ratatui::{
+    layout::{Constraint, Layout, Margin, Rect},
     style::{Color, Modifier, Style, Stylize},
     text::{Line, Span},
-    widgets::{
-        Block,
-        calendar::{CalendarEventStore, Monthly},
-    },
+    widgets::{Block, Cell, Paragraph, Row, Table, Wrap, calendar::CalendarEventStore},
 };
-use time::{Date, Duration, Month, OffsetDateTime, Weekday};
+use time::{Date, Month};
 
 use crate::{
-    panels::{Action, Panel, record_modal::RecordModalData},
-    store::Record,
+    config::config,
+    dates::{
+        add_days, add_months, add_years, clamp_navigable, display_date, month_bounds, today,
+        week_start, year_bounds,
+    },
+    holidays::{Country, holidays},
+    keymap::{Command, KeyContext},
+    panels::{
+        Action, Panel,
+        day_colors::{ColorBy, DayColors},
+        month_grid::MonthGrid,
+        record_modal::RecordModalData,
+    },
+    stats::{Stats, is_workday},
+    store::{Location, Record},
 };
 
 pub struct CalendarPanel {
     pub label: String,
     pub tag: String,
     pub records: Vec<Record>,
+    /// Locations of the records, work days are coloured by their category
+    pub locations: Vec<Location>,
     pub selected: Date,
+    /// Country whose public holidays are highlighted
+    pub country: Option<Country>,
+    /// What the colours of the work days stand for
+    pub color_by: ColorBy,
+    view: CalendarView,
 }
 
+/// How much of the calendar is shown around the selected day
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub enum CalendarView {
+    #[default]
+    Month,
+    Year,
+    /// A column per week, to spot gaps and long runs at a glance
+    Heatmap,
+    /// A row per day of the selected week, with its location, log type and note
+    Week,
+}
+
+/// Number of weeks in the heatmap
+pub const HEATMAP_WEEKS: i64 = 52;
+
+const HOLIDAY_STYLE: Style = Style::new()
+    .fg(Color::LightMagenta)
+    .add_modifier(Modifier::ITALIC);
+
+/// Height of a month grid: its title, the weekdays and six weeks
+const MONTH_HEIGHT: u16 = 8;
+
 impl CalendarPanel {
-    pub async fn new(records: Option<Vec<Record>>) -> Self {
+    pub async fn new(
+        records: Option<Vec<Record>>,
+        locations: Vec<Location>,
+        country: Option<Country>,
+    ) -> Self {
         CalendarPanel {
             label: "Calendar".to_string(),
             tag: " ²".to_string(),
             records: records.unwrap_or_default(),
-            selected: OffsetDateTime::now_utc().date(),
+            locations,
+            selected: today(),
+            country,
+            color_by: config().color_by,
+            view: CalendarView::Month,
         }
     }
-}
 
-fn bound_date_offset(date: Date, days: i64) -> Date {
-    // TODO: move date navigation into a 'bound' function, that can offset the date while
-    // staying inside month bound, another hotkey we'll use SHIFT+H and SHIFT+L to navigate months
-    let next_date = date.checked_add(Duration::days(days)).unwrap();
-    next_date
+    /// The styles of every day in the selected year
+    fn day_styles(&self, colors: &DayColors) -> CalendarEventStore {
+        let today_style = Style::default().add_modifier(Modifier::UNDERLINED);
+        let selected_style = Style::default().bg(Color::Red);
+        let weekend_style = Style::default().dark_gray();
+        let future_style = Style::default().fg(Color::Rgb(100, 100, 100));
+
+        let mut styles = CalendarEventStore::default();
+        let today = today();
+
+        // iterate over the year and higlight all days
+        let (first, last) = year_bounds(self.selected);
+        let mut current_day = Some(first);
+        while let Some(day) = current_day.filter(|d| *d <= last) {
+            if !is_workday(day) {
+                styles.add(day, weekend_style);
+            } else if day > today {
+                styles.add(day, future_style);
+            }
+            current_day = day.next_day();
+        }
+
+        // Public holidays
+        if let Some(country) = self.country {
+            for holiday in holidays(country, self.selected.year()) {
+                styles.add(holiday.date, HOLIDAY_STYLE);
+            }
+        }
+
+        // Logged days, keeping the holiday highlight
+        for record in &self.records {
+            let base = styles.0.get(&record.date).copied().unwrap_or_default();
+            let style = if base == HOLIDAY_STYLE {
+                HOLIDAY_STYLE.patch(colors.record_style(record))
+            } else {
+                colors.record_style(record)
+            };
+            styles.add(record.date, style);
+        }
+
+        // Today
+        let today_base = styles.0.get(&today).copied().unwrap_or_default();
+        styles.add(today, today_base.patch(today_style));
+
+        // Selected day
+        styles.add(self.selected, selected_style);
+        styles
+    }
+
+    /// The month of the selected day with a legend and the note of the day
+    fn render_month(
+        &self,
+        frame: &mut ratatui::Frame,
+        area: Rect,
+        styles: &CalendarEventStore,
+        colors: &DayColors,
+    ) {
+        let (first, last) = month_bounds(self.selected);
+        let records: Vec<Record> = self
+            .records
+            .iter()
+            .filter(|r| r.date >= first && r.date <= last)
+            .cloned()
+            .collect();
+        let noted: Vec<Date> = records
+            .iter()
+            .filter(|r| r.note.is_some())
+            .map(|r| r.date)
+            .collect();
+
+        // What the colours and markers of this month stand for
+        let mut legend = vec![Span::raw(format!("by {}: ", self.color_by.name())).dark_gray()];
+        let mut entries = colors.legend(&records);
+        if let Some(country) = self.country
+            && holidays(country, self.selected.year())
+                .iter()
+                .any(|h| h.date.month() == self.selected.month())
+        {
+            entries.push(("Public holiday".to_string(), HOLIDAY_STYLE));
+        }
+        for (label, style) in entries {
+            legend.push(Span::styled("■ ", style));
+            legend.push(Span::raw(format!("{label}  ")));
+        }
+        if !noted.is_empty() {
+            legend.push(Span::raw("• note"));
+        }
+
+        let cal = MonthGrid::new(self.selected, styles, config().first_weekday)
+            .show_weekdays_header(Style::default().bold())
+            .show_month_header(Style::default())
+            .show_surrounding(Style::default().dark_gray())
+            .mark_days(noted);
+
+        let [grid_area, legend_area, note_area] = Layout::vertical([
+            Constraint::Length(MONTH_HEIGHT),
+            Constraint::Length(2),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+        frame.render_widget(cal, grid_area);
+        frame.render_widget(
+            Paragraph::new(Line::from(legend)).wrap(Wrap { trim: true }),
+            legend_area.inner(Margin::new(1, 0)),
+        );
+
+        // the note of the selected day
+        if let Some(note) = records
+            .iter()
+            .find(|r| r.date == self.selected)
+            .and_then(|r| r.note.as_deref())
+        {
+            let note = Paragraph::new(format!("• {note}")).wrap(Wrap { trim: true });
+            frame.render_widget(note, note_area.inner(Margin::new(1, 0)));
+        }
+    }
+
+    /// The first day of the heatmap, showing the weeks up to today unless the selected day
+    /// falls outside of them
+    fn heatmap_first(&self) -> Date {
+        let first_weekday = config().first_weekday;
+        let weeks_before =
+            |date: Date| add_days(week_start(date, first_weekday), -7 * (HEATMAP_WEEKS - 1));
+        let today = today();
+        let first = weeks_before(today);
+        if self.selected >= first && self.selected <= today {
+            first
+        } else {
+            weeks_before(self.selected)
+        }
+    }
+
+    /// The last weeks as a grid of a column per week and a row per weekday
+    fn render_heatmap(&self, frame: &mut ratatui::Frame, area: Rect, colors: &DayColors) {
+        let today = today();
+        let first = self.heatmap_first();
+        let last = add_days(first, 7 * HEATMAP_WEEKS - 1);
+        let records: HashMap<Date, &Record> = self.records.iter().map(|r| (r.date, r)).collect();
+        let mut holiday_dates = Vec::new();
+        if let Some(country) = self.country {
+            for year in first.year()..=last.year() {
+                holiday_dates.extend(holidays(country, year).iter().map(|h| h.date));
+            }
+        }
+
+        // the name of a month above the week of its first day
+        let label_width = 3;
+        let mut months = " ".repeat(label_width + HEATMAP_WEEKS as usize);
+        let mut free_from = 0;
+        for week in 0..HEATMAP_WEEKS {
+            let week_first = add_days(first, 7 * week);
+            let Some(first_of_month) = (0..7)
+                .map(|day| add_days(week_first, day))
+                .find(|day| day.day() == 1)
+            else {
+                continue;
+            };
+            let position = label_width + week as usize;
+            if position >= free_from && position + 3 <= months.len() {
+                let name = &first_of_month.month().to_string()[..3];
+                months.replace_range(position..position + 3, name);
+                free_from = position + 4;
+            }
+        }
+
+        let mut lines = vec![Line::raw(months).dark_gray()];
+        let mut weekday = config().first_weekday;
+        for row in 0..7 {
+            let label = if row % 2 == 0 {
+                format!("{:.2} ", weekday.to_string())
+            } else {
+                " ".repeat(label_width)
+            };
+            let mut spans = vec![Span::raw(label).dark_gray()];
+            for week in 0..HEATMAP_WEEKS {
+                let day = add_days(first, 7 * week + row);
+                let (cell, mut style) = match records.get(&day) {
+                    Some(record) => ("■", colors.record_style(record)),
+                    None if holiday_dates.contains(&day) => ("■", HOLIDAY_STYLE),
+                    // a gap in the logging
+                    None if is_workday(day) && day <= today => ("□", Style::default().dark_gray()),
+                    None => ("·", Style::default().dark_gray()),
+                };
+                if day == today {
+                    style = style.add_modifier(Modifier::UNDERLINED);
+                }
+                if day == self.selected {
+                    style = style.bg(Color::Red);
+                }
+                spans.push(Span::styled(cell, style));
+            }
+            lines.push(Line::from(spans));
+            weekday = weekday.next();
+        }
+
+        // What the colours stand for and how much was logged
+        let shown: Vec<Record> = self
+            .records
+            .iter()
+            .filter(|r| r.date >= first && r.date <= last)
+            .cloned()
+            .collect();
+        let mut legend = vec![Span::raw(format!("by {}: ", self.color_by.name())).dark_gray()];
+        for (label, style) in colors.legend(&shown) {
+            legend.push(Span::styled("■ ", style));
+            legend.push(Span::raw(format!("{label}  ")));
+        }
+        legend.push(Span::raw("□ ").dark_gray());
+        legend.push(Span::raw("not logged"));
+        let stats = Stats::new(&self.records, &self.locations, first, last.min(today));
+        let summary = format!(
+            "{} to {}: {} workdays logged, {} not logged",
+            display_date(first),
+            display_date(last),
+            stats.logged_workdays,
+            stats.unlogged_workdays()
+        );
+
+        let [grid_area, legend_area] =
+            Layout::vertical([Constraint::Length(9), Constraint::Fill(1)]).areas(area);
+        frame.render_widget(Paragraph::new(lines), grid_area.inner(Margin::new(1, 0)));
+        frame.render_widget(
+            Paragraph::new(vec![Line::from(legend), Line::raw(summary)]).wrap(Wrap { trim: true }),
+            legend_area.inner(Margin::new(1, 0)),
+        );
+    }
+
+    /// The days of the selected week as rows of a table
+    fn render_week(&self, frame: &mut ratatui::Frame, area: Rect, colors: &DayColors) {
+        let first = week_start(self.selected, config().first_weekday);
+        let holiday_names: Vec<(Date, &str)> = match self.country {
+            Some(country) => (first.year()..=add_days(first, 6).year())
+                .flat_map(|year| holidays(country, year))
+                .map(|h| (h.date, h.name))
+                .collect(),
+            None => Vec::new(),
+        };
+
+        let rows = (0..7).map(|offset| {
+            let day = add_days(first, offset);
+            let date = format!("{:.3} {}", day.weekday().to_string(), display_date(day));
+            let week = format!("W{:02}", day.iso_week());
+            let holiday = holiday_names
+                .iter()
+                .find(|(date, _)| *date == day)
+                .map(|(_, name)| *name);
+            let mut cells = match self.records.iter().find(|r| r.date == day) {
+                Some(record) => {
+                    let location = self
+                        .locations
+                        .iter()
+                        .find(|l| l.id == record.location_id)
+                        .map_or("unknown".to_string(), |l| l.name.clone());
+                    vec![
+                        Cell::from(date),
+                        Cell::from(week),
+                        Cell::from(location).style(colors.record_style(record)),
+                        Cell::from(record.log_type.to_string()),
+                        Cell::from(record.note.clone().unwrap_or_default()),
+                    ]
+                }
+                None => vec![
+                    Cell::from(date),
+                    Cell::from(week),
+                    Cell::from(""),
+                    Cell::from(""),
+                    Cell::from(""),
+                ],
+            };
+            // a public holiday is named when the day has no note of its own
+            if let Some(name) = holiday
+                && self
+                    .records
+                    .iter()
+                    .all(|r| r.date != day || r.note.is_none())
+            {
+                cells[4] = Cell::from(Span::styled(name, HOLIDAY_STYLE));
+            }
+            let style = if day == self.selected {
+                Style::default().bg(Color::Red)
+            } else if !is_workday(day) {
+                Style::default().dark_gray()
+            } else {
+                Style::default()
+            };
+            Row::new(cells).style(style)
+        });
+
+        let header = Row::new(["Day", "Week", "Location", "Type", "Note"]).bold();
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(15),
+                Constraint::Length(4),
+                Constraint::Length(16),
+                Constraint::Length(8),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(header)
+        .column_spacing(2);
+        frame.render_widget(table, area.inner(Margin::new(1, 0)));
+    }
+
+    /// The twelve months of the selected year next to the totals of the year
+    fn render_year(&self, frame: &mut ratatui::Frame, area: Rect, styles: &CalendarEventStore) {
+        let [months_area, totals_area] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Length(30)])
+                .spacing(1)
+                .areas(area);
+
+        // as many months side by side as fit, scrolling to the selected month when the
+        // rows don't fit
+        let columns = (months_area.width / MonthGrid::WIDTH).clamp(1, 6);
+        let rows = 12_u16.div_ceil(columns);
+        let visible_rows = (months_area.height / MONTH_HEIGHT).clamp(1, rows);
+        let selected_row = (u8::from(self.selected.month()) as u16 - 1) / columns;
+        let first_row = selected_row.saturating_sub(visible_rows - 1);
+
+        let year = self.selected.year();
+        for row in first_row..first_row + visible_rows {
+            for column in 0..columns {
+                let index = row * columns + column;
+                let Ok(month) = Month::try_from(index as u8 + 1) else {
+                    break;
+                };
+                let header = if month == self.selected.month() {
+                    Style::default().bold().fg(config().focus_color)
+                } else {
+                    Style::default().bold()
+                };
+                let cal = MonthGrid::new(
+                    Date::from_calendar_date(year, month, 1).unwrap(),
+                    styles,
+                    config().first_weekday,
+                )
+                .show_month_header(header)
+                .show_weekdays_header(Style::default().dark_gray());
+                let month_area = Rect::new(
+                    months_area.x + column * MonthGrid::WIDTH,
+                    months_area.y + (row - first_row) * MONTH_HEIGHT,
+                    MonthGrid::WIDTH,
+                    MONTH_HEIGHT,
+                )
+                .intersection(months_area);
+                frame.render_widget(cal, month_area);
+            }
+        }
+
+        // Totals of the year
+        let (first, last) = year_bounds(self.selected);
+        let stats = Stats::new(&self.records, &self.locations, first, last);
+        let header = Style::default().bold();
+        let worked: usize = stats.locations.iter().map(|l| l.days).sum();
+
+        let mut lines = vec![Line::styled(format!("Totals {year}"), header)];
+        if stats.locations.is_empty() {
+            lines.push(Line::raw("  no work days logged").dark_gray());
+        }
+        for location in &stats.locations {
+            lines.push(Line::raw(format!(
+                "  {:<14} {:>3} {:>6.1}%",
+                location.name, location.days, location.percentage
+            )));
+        }
+        lines.push(Line::raw(""));
+        lines.push(Line::styled("Log types", header));
+        lines.push(Line::raw(format!("  Work       {worked:>3}")));
+        lines.push(Line::raw(format!("  Holiday    {:>3}", stats.holidays)));
+        lines.push(Line::raw(format!("  Vacation   {:>3}", stats.vacation)));
+        lines.push(Line::raw(format!("  Sick       {:>3}", stats.sick)));
+        lines.push(Line::raw(""));
+        lines.push(Line::styled("Workdays", header));
+        lines.push(Line::raw(format!("  This year  {:>3}", stats.workdays)));
+        lines.push(Line::raw(format!(
+            "  Logged     {:>3}",
+            stats.logged_workdays
+        )));
+        frame.render_widget(Paragraph::new(lines), totals_area);
+    }
 }
+
 impl Panel for CalendarPanel {
     fn update(&mut self, action: &Action) {
-        if let Action::LoadNavigateDateSuccess(records) = action {
-            self.records = records.clone();
+        match action {
+            Action::StartNavigateDate(date) => self.selected = *date,
+            Action::LoadRecordsSuccess(_, records) => self.records = records.clone(),
+            Action::AddRecordDbSuccess(record) => {
+                self.records.retain(|r| r.date != record.date);
+                self.records.push(record.clone());
+            }
+            Action::DeleteRecordDbSuccess(date) => self.records.retain(|r| r.date != *date),
+            Action::AddLocationDbSuccess(locations)
+            | Action::DeleteLocationDbSuccess(locations)
+            | Action::ArchiveLocationDbSuccess(locations)
+            | Action::EditLocationDbSuccess(locations) => self.locations = locations.clone(),
+            _ => {}
         }
     }
 
-    fn handle_input(&mut self, key_event: crossterm::event::KeyEvent) -> Option<Action> {
-        match key_event.code {
+    fn key_context(&self) -> KeyContext {
+        KeyContext::Calendar
+    }
+
+    fn handle_command(&mut self, command: Command, count: u32) -> Option<Action> {
+        let previous = self.selected;
+        let previous_view = self.view;
+        let count = i64::from(count);
+        match command {
             // Move to days
-            event::KeyCode::Char('j') => self.selected = bound_date_offset(self.selected, 7),
-            event::KeyCode::Char('k') => self.selected = bound_date_offset(self.selected, -7),
-            event::KeyCode::Char('h') => self.selected = bound_date_offset(self.selected, -1),
-            event::KeyCode::Char('l') => self.selected = bound_date_offset(self.selected, 1),
-
-            // Move Months
-            event::KeyCode::Char('H') => {
-                let prev_month = self.selected.saturating_sub(Duration::days(31));
-                return Some(Action::StartNavigateDate(prev_month));
-            }
-            event::KeyCode::Char('L') => {
-                let next_month = self.selected.saturating_add(Duration::days(31));
-                return Some(Action::StartNavigateDate(next_month));
-            }
-
-            // Today
-            event::KeyCode::Char('t') => self.selected = OffsetDateTime::now_utc().date(),
-            event::KeyCode::Char('D') => {}
-            event::KeyCode::Char('A') => {
-                return Some(Action::AddRecord(RecordModalData {
-                    location: "asdasd".to_string(),
-                    log_type: crate::store::LogType::Work,
-                    date: self.selected,
-                }));
+            // the days are rows in the week view, moving up and down goes a day
+            Command::NextWeek if self.view == CalendarView::Week => {
+                self.selected = add_days(self.selected, count);
+            }
+            Command::PrevWeek if self.view == CalendarView::Week => {
+                self.selected = add_days(self.selected, -count);
+            }
+            Command::NextWeek => self.selected = add_days(self.selected, 7 * count),
+            Command::PrevWeek => self.selected = add_days(self.selected, -7 * count),
+            Command::PrevDay => self.selected = add_days(self.selected, -count),
+            Command::NextDay => self.selected = add_days(self.selected, count),
+
+            // Move months and years, keeping the day when the month is long enough
+            Command::PrevMonth => self.selected = add_months(self.selected, -count),
+            Command::NextMonth => self.selected = add_months(self.selected, count),
+            Command::PrevYear => self.selected = add_years(self.selected, -count),
+            Command::NextYear => self.selected = add_years(self.selected, count),
+            Command::GotoDate => return Some(Action::OpenCommandLine("goto ".to_string())),
+
+            Command::Today => self.selected = today(),
+            Command::CycleColors => self.color_by = self.color_by.next(),
+            Command::ToggleYear => {
+                self.view = match self.view {
+                    CalendarView::Year => CalendarView::Month,
+                    _ => CalendarView::Year,
+                };
+            }
+            Command::ToggleWeek => {
+                self.view = match self.view {
+                    CalendarView::Week => CalendarView::Month,
+                    _ => CalendarView::Week,
+                };
+            }
+            Command::ToggleHeatmap => {
+                self.view = match self.view {
+                    CalendarView::Heatmap => CalendarView::Month,
+                    _ => CalendarView::Heatmap,
+                };
+            }
+            // drill into the month of the selected day
+            Command::Confirm if self.view != CalendarView::Month => {
+                self.view = CalendarView::Month;
+            }
+            Command::DeleteRecord => {
+                if self.records.iter().any(|r| r.date == self.selected) {
+                    return Some(Action::DeleteRecord(self.selected));
+                }
+                return None;
+            }
+            Command::Undo => return Some(Action::UndoDeleteRecord),
+            Command::AddRecord => {
+                // a logged day is edited, starting from its record
+                let existing = self.records.iter().find(|r| r.date == self.selected);
+                let data = match existing {
+                    Some(record) => RecordModalData {
+                        location: self
+                            .locations
+                            .iter()
+                            .find(|l| l.id == record.location_id)
+                            .map(|l| l.name.clone())
+                            .unwrap_or_default(),
+                        log_type: record.log_type.clone(),
+                        date: self.selected,
+                        note: record.note.clone(),
+                    },
+                    None => RecordModalData {
+                        location: String::new(),
+                        log_type: config().default_log_type.clone(),
+                        date: self.selected,
+                        note: None,
+                    },
+                };
+                return Some(Action::AddRecord(data));
             }
             _ => return None,
         }
+        self.selected = clamp_navigable(self.selected);
+        // Every move updates the stats of the selected month
+        if previous != self.selected {
+            return Some(Action::StartNavigateDate(self.selected));
+        }
+        // the year and the heatmap show more records than the month
+        if previous_view != self.view {
+            return Some(Action::ChangeCalendarView(self.view));
+        }
         Some(Action::Processing)
     }
 
     fn render(&mut self, frame: &mut ratatui::Frame, area: ratatui::layout::Rect, focussed: bool) {
         let label = Span::raw(self.label.clone());
-        let tag_style = Style::default().fg(ratatui::style::Color::LightRed);
+        let tag_style = Style::default().fg(config().focus_color);
         let tagspan = Span::raw(&self.tag).style(tag_style.bold());
 
         let title = Line::raw("").spans([tagspan, label]);
@@ -88,7 +588,7 @@ impl Panel for CalendarPanel {
         let block_inner = block.inner(area);
 
         if focussed {
-            block = block.border_style(Style::default().fg(ratatui::style::Color::LightRed));
+            block = block.border_style(Style::default().fg(config().focus_color));
             // list = list.highlight_style(
             //     Style::new()
             //         .fg(ratatui::style::Color::LightRed)
@@ -101,44 +601,19 @@ impl Panel for CalendarPanel {
             // frame.render_stateful_widget(list, block_inner, &mut self.state);
         }
 
-        let today_style = Style::default().add_modifier(Modifier::UNDERLINED);
-        let surrounding = Style::default().dark_gray();
-        let cal_headers = Style::default().bold();
-        let _holiday_style = Style::default().fg(Color::LightMagenta);
-        let selected_style = Style::default().bg(Color::Red);
-        let weekend_style = Style::default().dark_gray();
-        let future_style = Style::default().fg(Color::Rgb(100, 100, 100));
-
-        let mut styles = CalendarEventStore::today(today_style);
-
-        // TODO: convert dates from records into styled dates
+        frame.render_widget(block, area);
 
-        // iterate over month and higlight all days
-        let first_day =
-            Date::from_calendar_date(self.selected.year(), self.selected.month(), 1).unwrap();
-        for i in 0..31 {
-            if let Some(current_day) = first_day.checked_add(Duration::days(i)) {
-                if [Weekday::Saturday, Weekday::Sunday].contains(&current_day.weekday()) {
-                    styles.add(current_day, weekend_style);
-                } else if current_day > OffsetDateTime::now_utc().date() {
-                    styles.add(current_day, future_style);
-                }
-            }
+        let colors = DayColors::new(self.color_by, &self.locations);
+        let styles = self.day_styles(&colors);
+        match self.view {
+            CalendarView::Month => self.render_month(frame, block_inner, &styles, &colors),
+            CalendarView::Year => self.render_year(frame, block_inner, &styles),
+            CalendarView::Heatmap => self.render_heatmap(frame, block_inner, &colors),
+            CalendarView::Week => self.render_week(frame, block_inner, &colors),
         }
+    }
 
-        // Selected day
-        styles.add(self.selected, selected_style);
-
-        let d = Date::from_calendar_date(2026, Month::January, 1).unwrap();
-        let cal = Monthly::new(d, styles)
-            .show_weekdays_header(cal_headers)
-            .show_month_header(Style::default())
-            .show_surrounding(surrounding);
-
-        // let cal = Monthly::new(d, CalendarEventStore::default());
-
-        frame.render_widget(block, area);
-
-        frame.render_widget(cal, block_inner);
+    fn maximized(&self) -> bool {
+        self.view == CalendarView::Year
     }
 }
@@ -0,0 +1,236 @@
+use std::{fs, path::PathBuf};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Position, Rect},
+    text::{Line, Span},
+};
+
+use crate::{
+    line_command::{LineCommand, last_word_start, split_words},
+    panels::textfield_component::{TextField, TextFieldResult},
+    store::Location,
+};
+
+/// Number of commands kept in the history file
+const HISTORY_SIZE: usize = 200;
+
+pub enum CommandLineResult {
+    Editting,
+    Cancelled,
+    Confirmed(String),
+}
+
+/// Candidates of the word being completed, cycled through with tab
+struct Completion {
+    /// The line up to the completed word
+    base: String,
+    candidates: Vec<String>,
+    index: usize,
+}
+
+/// The `:` command line at the bottom of the screen, with history and tab completion
+pub struct CommandLine {
+    pub active: bool,
+    field: TextField,
+    history: Vec<String>,
+    history_path: PathBuf,
+    /// Position in the history while browsing it with up and down
+    history_index: Option<usize>,
+    locations: Vec<Location>,
+    completion: Option<Completion>,
+}
+
+impl CommandLine {
+    /// A command line keeping its history in `history_path`
+    pub fn new(history_path: PathBuf) -> Self {
+        let history = fs::read_to_string(&history_path)
+            .map(|content| content.lines().map(str::to_string).collect())
+            .unwrap_or_default();
+        CommandLine {
+            active: false,
+            field: TextField::new(String::new(), None),
+            history,
+            history_path,
+            history_index: None,
+            locations: Vec::new(),
+            completion: None,
+        }
+    }
+
+    /// Start typing a command after `line`, `locations` are used for completion
+    pub fn open(&mut self, locations: Vec<Location>, line: String) {
+        self.active = true;
+        self.field.clear();
+        self.set_line(line);
+        self.history_index = None;
+        self.locations = locations;
+        self.completion = None;
+    }
+
+    fn set_line(&mut self, line: String) {
+        self.field.state.value = line;
+        self.field.move_cursor_end();
+    }
+
+    fn remember(&mut self, line: &str) {
+        if line.trim().is_empty() || self.history.last().is_some_and(|last| last == line) {
+            return;
+        }
+        self.history.push(line.to_string());
+        if self.history.len() > HISTORY_SIZE {
+            self.history.remove(0);
+        }
+        if let Some(parent) = self.history_path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        let _ = fs::write(&self.history_path, self.history.join("\n") + "\n");
+    }
+
+    fn browse_history(&mut self, older: bool) {
+        let index = match (self.history_index, older) {
+            (None, true) => self.history.len().checked_sub(1),
+            (None, false) => None,
+            (Some(index), true) => Some(index.saturating_sub(1)),
+            (Some(index), false) => Some(index + 1).filter(|i| *i < self.history.len()),
+        };
+        self.history_index = index;
+        let line = index
+            .and_then(|i| self.history.get(i))
+            .cloned()
+            .unwrap_or_default();
+        self.set_line(line);
+    }
+
+    /// Complete the last word, pressing tab again gives the next candidate
+    fn complete(&mut self) {
+        if let Some(completion) = &mut self.completion {
+            completion.index = (completion.index + 1) % completion.candidates.len();
+            let line = format!(
+                "{}{}",
+                completion.base, completion.candidates[completion.index]
+            );
+            self.set_line(line);
+            return;
+        }
+        let line = self.field.state.value.clone();
+        let start = last_word_start(&line);
+        let (base, partial) = line.split_at(start);
+        let Ok(words) = split_words(base) else {
+            return;
+        };
+        let partial = partial.trim_start_matches('"').to_lowercase();
+        let candidates: Vec<String> = LineCommand::completions(&words, &self.locations)
+            .into_iter()
+            .filter(|c| {
+                c.trim_start_matches('"')
+                    .to_lowercase()
+                    .starts_with(&partial)
+            })
+            .collect();
+        let Some(first) = candidates.first() else {
+            return;
+        };
+        let mut completed = format!("{base}{first}");
+        if candidates.len() == 1 {
+            completed.push(' ');
+        } else {
+            self.completion = Some(Completion {
+                base: base.to_string(),
+                candidates,
+                index: 0,
+            });
+        }
+        self.set_line(completed);
+    }
+
+    pub fn handle_input(&mut self, key_event: KeyEvent) -> CommandLineResult {
+        if key_event.code != KeyCode::Tab {
+            self.completion = None;
+        }
+        match key_event.code {
+            KeyCode::Tab => self.complete(),
+            KeyCode::Up => self.browse_history(true),
+            KeyCode::Down => self.browse_history(false),
+            // leave the command line when deleting the ':'
+            KeyCode::Backspace if self.field.state.value.is_empty() => {
+                self.active = false;
+                return CommandLineResult::Cancelled;
+            }
+            _ => match self.field.handle_input(key_event) {
+                TextFieldResult::Editting => {}
+                TextFieldResult::Cancelled => {
+                    self.active = false;
+                    return CommandLineResult::Cancelled;
+                }
+                TextFieldResult::Confirm(state) => {
+                    self.active = false;
+                    self.remember(&state.value);
+                    return CommandLineResult::Confirmed(state.value);
+                }
+            },
+        }
+        CommandLineResult::Editting
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let line = Line::from(vec![Span::raw(":"), Span::raw(&self.field.state.value)]);
+        frame.render_widget(line, area);
+        let cursor = self.field.state.value[..self.field.byte_index()]
+            .chars()
+            .count() as u16;
+        frame.set_cursor_position(Position::new(area.x + 1 + cursor, area.y));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyModifiers;
+
+    use super::*;
+    use crate::store::Category;
+
+    fn command_line(line: &str) -> CommandLine {
+        let mut command_line = CommandLine::new(PathBuf::from("/nonexistent/locwork-history"));
+        let locations = ["Home", "New York", "Newport"].map(|name| Location {
+            id: 0,
+            name: name.to_string(),
+            tag: String::new(),
+            archived: false,
+            category: Category::Unknown,
+        });
+        command_line.open(locations.to_vec(), line.to_string());
+        command_line
+    }
+
+    fn tab(command_line: &mut CommandLine) -> String {
+        command_line.handle_input(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE));
+        command_line.field.state.value.clone()
+    }
+
+    #[test]
+    fn completes_a_single_candidate_with_a_space() {
+        let mut line = command_line("log today h");
+        assert_eq!(tab(&mut line), "log today Home ");
+        let mut line = command_line("ex");
+        assert_eq!(tab(&mut line), "export ");
+    }
+
+    #[test]
+    fn tab_cycles_through_the_candidates() {
+        let mut line = command_line("lo");
+        assert_eq!(tab(&mut line), "log");
+        assert_eq!(tab(&mut line), "loc");
+        let mut line = command_line("log today new");
+        assert_eq!(tab(&mut line), "log today \"New York\"");
+        assert_eq!(tab(&mut line), "log today Newport");
+        assert_eq!(tab(&mut line), "log today \"New York\"");
+    }
+
+    #[test]
+    fn completes_a_quoted_word() {
+        let mut line = command_line("log today \"New Y");
+        assert_eq!(tab(&mut line), "log today \"New York\" ");
+    }
+}
@@ -0,0 +1,65 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Style, Stylize},
+    widgets::{Block, Clear, Paragraph, Wrap},
+};
+
+use crate::{
+    config::config,
+    keymap::{Command, KeyContext},
+    panels::Action,
+};
+
+/// Asks a yes/no question before running an action
+pub struct ConfirmModal {
+    title: String,
+    question: String,
+    action: Option<Action>,
+}
+
+impl ConfirmModal {
+    /// Ask `question`, `action` is the result when it is confirmed
+    pub fn new(title: String, question: String, action: Action) -> Self {
+        ConfirmModal {
+            title,
+            question,
+            action: Some(action),
+        }
+    }
+
+    pub fn handle_command(&mut self, command: Command) -> Option<Action> {
+        match command {
+            Command::Confirm => self.action.take(),
+            Command::Cancel => Some(Action::CancelModal),
+            _ => None,
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::bordered().title(self.title.as_str());
+        let inner = block.inner(area);
+        let [question_area, help_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(inner);
+
+        let keymap = &config().keymap;
+        let help: Vec<String> = [(Command::Confirm, "yes"), (Command::Cancel, "no")]
+            .iter()
+            .filter_map(|(command, label)| {
+                let key = keymap.key_for(KeyContext::Prompt, *command)?;
+                Some(format!("{key}: {label}"))
+            })
+            .collect();
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+        frame.render_widget(
+            Paragraph::new(self.question.as_str()).wrap(Wrap { trim: true }),
+            question_area,
+        );
+        frame.render_widget(
+            Paragraph::new(help.join("  ")).style(Style::default().dark_gray()),
+            help_area,
+        );
+    }
+}
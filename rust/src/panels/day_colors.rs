@@ -0,0 +1,154 @@
+use clap::ValueEnum;
+use ratatui::style::{Color, Style};
+
+use crate::{
+    config::config,
+    store::{Category, Location, LogType, Record},
+};
+
+/// What the colour of a work day in the calendars stands for
+#[derive(Clone, Copy, PartialEq, Eq, Debug, ValueEnum)]
+pub enum ColorBy {
+    /// The category of the location, e.g. home or office
+    Category,
+    /// Every location gets its own colour
+    Location,
+    /// Only the log type, every work day looks the same
+    LogType,
+}
+
+impl ColorBy {
+    /// The next mode, to switch between them with a single key
+    pub fn next(self) -> Self {
+        match self {
+            ColorBy::Category => ColorBy::Location,
+            ColorBy::Location => ColorBy::LogType,
+            ColorBy::LogType => ColorBy::Category,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ColorBy::Category => "category",
+            ColorBy::Location => "location",
+            ColorBy::LogType => "log type",
+        }
+    }
+}
+
+/// Colours handed out to the locations in the order they were added
+const LOCATION_PALETTE: [Color; 8] = [
+    Color::LightGreen,
+    Color::LightBlue,
+    Color::Yellow,
+    Color::LightRed,
+    Color::Cyan,
+    Color::Green,
+    Color::Blue,
+    Color::Magenta,
+];
+
+fn log_type_color(log_type: &LogType) -> Option<Color> {
+    match log_type {
+        LogType::Unknown => None,
+        LogType::Work => Some(Color::LightGreen),
+        LogType::Holiday => Some(Color::LightMagenta),
+        LogType::Vacation => Some(Color::LightCyan),
+        LogType::Sick => Some(Color::LightYellow),
+    }
+}
+
+fn category_color(category: Category) -> Option<Color> {
+    match category {
+        Category::Unknown => log_type_color(&LogType::Work),
+        Category::Home => Some(Color::Green),
+        Category::Office => Some(Color::Yellow),
+        Category::Client => Some(Color::LightBlue),
+        Category::Travel => Some(Color::LightRed),
+    }
+}
+
+/// Styles of the logged days, shared by the calendar views
+pub struct DayColors<'a> {
+    pub color_by: ColorBy,
+    locations: &'a [Location],
+}
+
+impl<'a> DayColors<'a> {
+    pub fn new(color_by: ColorBy, locations: &'a [Location]) -> Self {
+        DayColors {
+            color_by,
+            locations,
+        }
+    }
+
+    /// A colour from the `colors` table of the config, or the default one
+    fn color(name: &str, default: Option<Color>) -> Option<Color> {
+        config()
+            .colors
+            .get(&name.to_lowercase())
+            .copied()
+            .or(default)
+    }
+
+    fn location(&self, record: &Record) -> Option<&Location> {
+        self.locations.iter().find(|l| l.id == record.location_id)
+    }
+
+    /// The category of the record's location, uncategorized work days are plain work days
+    fn category(&self, record: &Record) -> Option<Category> {
+        self.location(record)
+            .map(|l| l.category)
+            .filter(|c| *c != Category::Unknown)
+    }
+
+    /// The name shown in the legend for the days that look like `record`
+    pub fn label(&self, record: &Record) -> String {
+        if record.log_type != LogType::Work {
+            return record.log_type.to_string();
+        }
+        match (self.color_by, self.category(record), self.location(record)) {
+            (ColorBy::Category, Some(category), _) => category.to_string(),
+            (ColorBy::Location, _, Some(location)) => location.name.clone(),
+            _ => record.log_type.to_string(),
+        }
+    }
+
+    /// The style of a logged day
+    pub fn record_style(&self, record: &Record) -> Style {
+        let color = if record.log_type != LogType::Work {
+            Self::color(
+                &record.log_type.to_string(),
+                log_type_color(&record.log_type),
+            )
+        } else {
+            match (self.color_by, self.category(record), self.location(record)) {
+                (ColorBy::Category, Some(category), _) => {
+                    Self::color(&category.to_string(), category_color(category))
+                }
+                (ColorBy::Location, _, Some(location)) => {
+                    let index = self.locations.iter().filter(|l| l.id < location.id).count();
+                    Self::color(
+                        &location.name,
+                        Some(LOCATION_PALETTE[index % LOCATION_PALETTE.len()]),
+                    )
+                }
+                _ => Self::color("work", log_type_color(&LogType::Work)),
+            }
+        };
+        color.map_or(Style::default(), |color| Style::default().fg(color))
+    }
+
+    /// The labels and styles of the given records, once each and sorted by label
+    pub fn legend(&self, records: &[Record]) -> Vec<(String, Style)> {
+        let mut entries: Vec<(String, Style)> = Vec::new();
+        for record in records {
+            let label = self.label(record);
+            if !entries.iter().any(|(l, _)| *l == label) {
+                entries.push((label, self.record_style(record)));
+            }
+        }
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        entries
+    }
+}
@@ -1,8 +1,12 @@
-use crossterm::event::KeyEvent;
+use crate::config::config;
 use ratatui::{Frame, style::Style, text::Span, widgets::Block};
 
-use crate::panels::{Action, Panel};
+use crate::{
+    keymap::{Command, KeyContext},
+    panels::{Action, Panel},
+};
 
+#[allow(dead_code)]
 pub struct DebugPanel {
     pub title: String,
 }
@@ -13,12 +17,16 @@ impl Panel for DebugPanel {
         let inner = block.inner(area);
         let span = Span::raw("this is a span");
         if focussed {
-            block = block.border_style(Style::default().fg(ratatui::style::Color::LightRed));
+            block = block.border_style(Style::default().fg(config().focus_color));
         }
         frame.render_widget(block, area);
         frame.render_widget(span, inner);
     }
-    fn handle_input(&mut self, _key: KeyEvent) -> Option<Action> {
+    fn key_context(&self) -> KeyContext {
+        KeyContext::Status
+    }
+
+    fn handle_command(&mut self, _command: Command, _count: u32) -> Option<Action> {
         None
     }
 }
@@ -0,0 +1,73 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Block, Clear, Paragraph},
+};
+
+use crate::{
+    config::config,
+    keymap::{Command, KeyContext, Keymap},
+};
+
+/// Overlay listing the key bindings of the focussed panel or modal
+pub struct HelpModal {
+    lines: Vec<Line<'static>>,
+    scroll: u16,
+}
+
+impl HelpModal {
+    /// Help for the bindings of `contexts`, in the order they are looked up
+    pub fn new(contexts: &[KeyContext], keymap: &Keymap) -> Self {
+        let mut lines = Vec::new();
+        for context in contexts {
+            let commands = keymap.commands(*context);
+            if commands.is_empty() {
+                continue;
+            }
+            if !lines.is_empty() {
+                lines.push(Line::raw(""));
+            }
+            lines.push(Line::styled(
+                context.title(),
+                Style::default().fg(config().focus_color).bold(),
+            ));
+            let keys: Vec<String> = commands.iter().map(|(_, keys)| keys.join(", ")).collect();
+            let width = keys.iter().map(|k| k.chars().count()).max().unwrap_or(0);
+            for ((command, _), keys) in commands.iter().zip(keys) {
+                lines.push(Line::raw(format!(
+                    "  {keys:<width$}  {}",
+                    command.description()
+                )));
+            }
+        }
+        HelpModal { lines, scroll: 0 }
+    }
+
+    /// Scroll through the bindings, returns false when the overlay should close
+    pub fn handle_command(&mut self, command: Command, count: u32) -> bool {
+        let count = count.try_into().unwrap_or(u16::MAX);
+        let last = self
+            .lines
+            .len()
+            .saturating_sub(1)
+            .try_into()
+            .unwrap_or(u16::MAX);
+        match command {
+            Command::SelectNext => self.scroll = self.scroll.saturating_add(count).min(last),
+            Command::SelectPrev => self.scroll = self.scroll.saturating_sub(count),
+            Command::Cancel | Command::Help => return false,
+            _ => {}
+        }
+        true
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let help = Paragraph::new(self.lines.clone())
+            .scroll((self.scroll, 0))
+            .block(Block::bordered().title("Help"));
+        frame.render_widget(Clear, area);
+        frame.render_widget(help, area);
+    }
+}
@@ -1,4 +1,4 @@
-use crossterm::event::{KeyCode, KeyEvent};
+use crate::keymap::Command;
 use ratatui::{
     Frame,
     layout::Rect,
@@ -27,33 +27,39 @@ impl SimpleListPicker {
         }
     }
 
-    /// Select the default (first) item in the list
+    /// Select the default item in the list, which is the `selected` value or the first item
     pub fn select_default(&mut self) {
-        self.list_widget.select_first();
+        match self.values.iter().position(|v| *v == self.selected) {
+            Some(index) => self.list_widget.select(Some(index)),
+            None => self.list_widget.select_first(),
+        }
     }
 
-    pub fn handle_input(&mut self, key_event: KeyEvent) -> SimpleListPickerResult {
-        if key_event.code.is_esc() {
-            return SimpleListPickerResult::Cancelled;
-        }
-        if key_event.code.is_enter() {
-            return SimpleListPickerResult::Confirmed(self.selected.clone());
+    fn sync_selected(&mut self) {
+        if let Some(value) = self
+            .list_widget
+            .selected()
+            .and_then(|index| self.values.get(index))
+        {
+            self.selected = value.clone();
         }
-        match key_event.code {
-            KeyCode::Char('q') => {
-                return SimpleListPickerResult::Cancelled;
-            } // up
-            KeyCode::Char('k') => {
-                self.list_widget.select_previous();
-                return SimpleListPickerResult::Editting;
-            } // up
-            //
-            KeyCode::Char('j') => {
-                self.list_widget.select_next();
-                return SimpleListPickerResult::Editting;
+    }
+
+    pub fn handle_command(&mut self, command: Command, count: u32) -> SimpleListPickerResult {
+        let count = count.try_into().unwrap_or(u16::MAX);
+        match command {
+            Command::Cancel => return SimpleListPickerResult::Cancelled,
+            Command::Confirm => {
+                self.sync_selected();
+                return SimpleListPickerResult::Confirmed(self.selected.clone());
             }
-            _ => return SimpleListPickerResult::Editting,
-        };
+            Command::SelectPrev => self.list_widget.scroll_up_by(count),
+            Command::SelectNext => self.list_widget.scroll_down_by(count),
+            Command::SelectFirst => self.list_widget.select_first(),
+            Command::SelectLast => self.list_widget.select_last(),
+            _ => {}
+        }
+        SimpleListPickerResult::Editting
     }
     pub fn render(&mut self, frame: &mut Frame, area: Rect) {
         frame.render_widget(Clear, area);
@@ -1,14 +1,22 @@
 use crossterm::event::KeyEvent;
 use ratatui::Frame;
-use ratatui::layout::Rect;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Clear, Paragraph, Wrap};
 
-use crate::panels::textfield_component::{TextField, TextFieldResult};
+use crate::config::config;
+use crate::keymap::{Command, KeyContext};
+use crate::panels::textfield_component::TextField;
+use crate::store::{Category, Location};
 
 // #[derive(Eq, PartialEq)]
 pub struct AddLocationModal {
     pub state: LocationModalState,
     // pub input: String,
     //character_index: usize,
+    /// Names of the existing locations, the new name must not be one of them
+    taken: Vec<String>,
     location_field: TextField,
 }
 
@@ -20,16 +28,16 @@ pub struct LocationModalState {
 
 #[derive(Eq, PartialEq)]
 pub enum InputModalResult {
-    Editting,
     Cancelled,
     Confirmed(String),
 }
 
 impl AddLocationModal {
     // https://ratatui.rs/examples/apps/user_input/
-    pub fn new() -> Self {
+    pub fn new(locations: &[Location]) -> Self {
         Self {
             state: LocationModalState::default(),
+            taken: locations.iter().map(|l| l.name.clone()).collect(),
             location_field: TextField::new("Add Location".to_string(), None),
         }
     }
@@ -38,20 +46,262 @@ impl AddLocationModal {
         self.state.text.clear();
     }
 
-    pub fn handle_input(&mut self, key_event: KeyEvent) -> Option<InputModalResult> {
-        match self.location_field.handle_input(key_event) {
-            TextFieldResult::Editting => {
-                self.state.text = self.location_field.state.value.clone();
-                Some(InputModalResult::Editting)
+    /// Handle a command of the `LocationModal` key context
+    pub fn handle_command(&mut self, command: Command) -> Option<InputModalResult> {
+        match command {
+            Command::Confirm if name_problem(&self.state.text, &self.taken).is_none() => Some(
+                InputModalResult::Confirmed(self.state.text.trim().to_string()),
+            ),
+            Command::Cancel => Some(InputModalResult::Cancelled),
+            _ => None,
+        }
+    }
+
+    /// Type a key that isn't bound into the name
+    pub fn handle_input(&mut self, key_event: KeyEvent) {
+        self.location_field.handle_input(key_event);
+        self.state.text = self.location_field.state.value.clone();
+    }
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        self.location_field.render(frame, area);
+        if let Some(problem) = name_problem(&self.state.text, &self.taken) {
+            let inner = Block::bordered().inner(area);
+            let [_, problem_area] =
+                Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(inner);
+            frame.render_widget(Line::raw(problem).light_red(), problem_area);
+        }
+    }
+}
+
+/// Why a typed location name can't be saved, if it can't
+fn name_problem(name: &str, taken: &[String]) -> Option<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Some("the name can't be empty".to_string());
+    }
+    if taken.iter().any(|taken| taken == name) {
+        return Some(format!("location '{name}' already exists"));
+    }
+    None
+}
+
+/// The part of the edit modal that takes the keys
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum EditField {
+    Name,
+    Tag,
+    Category,
+}
+
+/// Renames a location and changes its tag and category
+pub struct EditLocationModal {
+    location: Location,
+    /// Names of the other locations, a new name must not be one of them
+    taken: Vec<String>,
+    name_field: TextField,
+    tag_field: TextField,
+    category: Category,
+    field: EditField,
+}
+
+impl EditLocationModal {
+    pub fn new(location: Location, locations: &[Location]) -> Self {
+        let taken = locations
+            .iter()
+            .filter(|l| l.id != location.id)
+            .map(|l| l.name.clone())
+            .collect();
+        let mut name_field = TextField::new("Name".to_string(), Some(location.name.clone()));
+        name_field.move_cursor_end();
+        let mut tag_field = TextField::new("Tag".to_string(), Some(location.tag.clone()));
+        tag_field.move_cursor_end();
+        Self {
+            category: location.category,
+            location,
+            taken,
+            name_field,
+            tag_field,
+            field: EditField::Name,
+        }
+    }
+
+    /// Why the typed name can't be saved, if it can't
+    fn problem(&self) -> Option<String> {
+        name_problem(&self.name_field.state.value, &self.taken)
+    }
+
+    /// Move to the next or previous field
+    fn cycle_field(&mut self, forward: bool) {
+        let fields = [EditField::Name, EditField::Tag, EditField::Category];
+        let index = fields.iter().position(|f| *f == self.field).unwrap_or(0);
+        let next = if forward {
+            (index + 1) % fields.len()
+        } else {
+            (index + fields.len() - 1) % fields.len()
+        };
+        self.field = fields[next];
+    }
+
+    /// Pick the next or previous category
+    fn cycle_category(&mut self, forward: bool) {
+        let all = Category::ALL;
+        let index = all.iter().position(|c| *c == self.category).unwrap_or(0);
+        let next = if forward {
+            (index + 1) % all.len()
+        } else {
+            (index + all.len() - 1) % all.len()
+        };
+        self.category = all[next];
+    }
+
+    /// Handle a command of the `LocationModal` key context, the edited location is returned
+    /// once it is confirmed
+    pub fn handle_command(&mut self, command: Command) -> Option<EditLocationResult> {
+        match command {
+            Command::NextField => self.cycle_field(true),
+            Command::PrevField => self.cycle_field(false),
+            Command::NextCategory if self.field == EditField::Category => self.cycle_category(true),
+            Command::PrevCategory if self.field == EditField::Category => {
+                self.cycle_category(false)
             }
-            TextFieldResult::Cancelled => Some(InputModalResult::Cancelled),
-            TextFieldResult::Confirm(ref state) => {
-                self.state.text = state.value.clone();
-                Some(InputModalResult::Confirmed(self.state.text.clone()))
+            Command::Cancel => return Some(EditLocationResult::Cancelled),
+            Command::Confirm if self.problem().is_none() => {
+                return Some(EditLocationResult::Confirmed(Location {
+                    name: self.name_field.state.value.trim().to_string(),
+                    tag: self.tag_field.state.value.trim().to_string(),
+                    category: self.category,
+                    ..self.location.clone()
+                }));
             }
+            _ => {}
         }
+        None
+    }
+
+    /// Type a key that isn't bound into the focussed field
+    pub fn handle_input(&mut self, key_event: KeyEvent) {
+        match self.field {
+            EditField::Name => self.name_field.handle_input(key_event),
+            EditField::Tag => self.tag_field.handle_input(key_event),
+            EditField::Category => return,
+        };
     }
+
     pub fn render(&self, frame: &mut Frame, area: Rect) {
-        self.location_field.render(frame, area);
+        let block = Block::bordered().title(format!("Edit Location {}", self.location.name));
+        let inner = block.inner(area);
+        let [
+            name_area,
+            problem_area,
+            tag_area,
+            category_area,
+            _,
+            help_area,
+        ] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Length(1),
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Fill(1),
+            Constraint::Length(2),
+        ])
+        .areas(inner);
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+        if self.field == EditField::Name {
+            self.name_field.render_focussed(frame, name_area);
+        } else {
+            self.name_field.render(frame, name_area);
+        }
+        if self.field == EditField::Tag {
+            self.tag_field.render_focussed(frame, tag_area);
+        } else {
+            self.tag_field.render(frame, tag_area);
+        }
+        let mut category_block = Block::bordered().title("Category");
+        if self.field == EditField::Category {
+            category_block = category_block.border_style(Style::default().fg(config().focus_color));
+        }
+        frame.render_widget(
+            Paragraph::new(format!("< {} >", self.category)).block(category_block),
+            category_area,
+        );
+        if let Some(problem) = self.problem() {
+            frame.render_widget(Line::raw(problem).light_red(), problem_area);
+        }
+        let keymap = &config().keymap;
+        let help: Vec<String> = [
+            (Command::NextField, "next field"),
+            (Command::NextCategory, "category"),
+            (Command::Confirm, "save"),
+            (Command::Cancel, "cancel"),
+            (Command::Help, "keys"),
+        ]
+        .iter()
+        .filter_map(|(command, label)| {
+            let key = keymap.key_for(KeyContext::LocationModal, *command)?;
+            Some(format!("{key}: {label}"))
+        })
+        .collect();
+        frame.render_widget(
+            Paragraph::new(help.join("  "))
+                .wrap(Wrap { trim: true })
+                .dark_gray(),
+            help_area,
+        );
+    }
+}
+
+pub enum EditLocationResult {
+    Cancelled,
+    Confirmed(Location),
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    use super::*;
+    use crate::keymap::{Dispatch, KeyDispatcher, Keymap};
+
+    #[test]
+    fn digits_are_typed_into_the_name() {
+        let mut modal = AddLocationModal::new(&[]);
+        assert!(type_keys(&mut modal, "B42") == Some(InputModalResult::Confirmed("B42".into())));
+    }
+
+    #[test]
+    fn new_names_are_trimmed_and_checked() {
+        let beach = Location {
+            id: 1,
+            name: "Beach".to_string(),
+            tag: String::new(),
+            archived: false,
+            category: Category::Unknown,
+        };
+        let mut modal = AddLocationModal::new(&[beach]);
+        assert!(type_keys(&mut modal, "   ").is_none());
+        modal.clear();
+        assert!(type_keys(&mut modal, " Beach ").is_none());
+        modal.clear();
+        let result = type_keys(&mut modal, " Home ");
+        assert!(result == Some(InputModalResult::Confirmed("Home".into())));
+    }
+
+    /// Type `text` into the modal and press enter
+    fn type_keys(modal: &mut AddLocationModal, text: &str) -> Option<InputModalResult> {
+        let keymap = Keymap::default();
+        let mut keys = KeyDispatcher::default();
+        let mut result = None;
+        for code in text.chars().map(KeyCode::Char).chain([KeyCode::Enter]) {
+            let key = KeyEvent::new(code, KeyModifiers::NONE);
+            match keys.feed(key, &keymap, &[KeyContext::LocationModal]) {
+                Dispatch::Matched(command, _) => result = modal.handle_command(command),
+                Dispatch::Unmatched => modal.handle_input(key),
+                Dispatch::Pending => {}
+            }
+        }
+        result
     }
 }
@@ -1,12 +1,13 @@
-use crossterm::event::{self, KeyEvent};
 use ratatui::{
     Frame,
     style::{Modifier, Style, Stylize},
     text::{Line, Span},
-    widgets::{Block, List, ListState},
+    widgets::{Block, List, ListItem, ListState},
 };
 
 use crate::{
+    config::config,
+    keymap::{Command, KeyContext},
     panels::{Action, location_modal::LocationModalState, panel::Panel},
     store::Location,
 };
@@ -15,6 +16,8 @@ pub struct LocationsPanel {
     pub label: String,
     pub locations: Vec<Location>,
     pub tag: String,
+    /// Whether the archived locations are listed too
+    show_archived: bool,
     state: ListState,
 }
 
@@ -22,26 +25,76 @@ impl LocationsPanel {
     pub async fn new(locations: Vec<Location>) -> Self {
         let mut base = LocationsPanel {
             label: "Locations".to_string(),
-            locations: locations,
+            locations,
             tag: " ¹".to_string(),
+            show_archived: false,
             state: ListState::default(),
         };
         base.state.select_first();
         base
     }
+
+    /// The listed locations, the archived ones are left out unless they are shown
+    fn visible(&self) -> Vec<&Location> {
+        self.locations
+            .iter()
+            .filter(|l| self.show_archived || !l.archived)
+            .collect()
+    }
+
+    fn selected(&self) -> Option<&Location> {
+        self.visible().get(self.state.selected()?).copied()
+    }
+
+    /// Select the location named `name` if it is listed, keeping the selection in range
+    /// otherwise
+    fn reselect(&mut self, name: Option<String>) {
+        let visible = self.visible();
+        let index = name
+            .and_then(|name| visible.iter().position(|l| l.name == name))
+            .or(self.state.selected())
+            .map(|i| i.min(visible.len().saturating_sub(1)));
+        self.state.select(index);
+    }
 }
 
 impl Panel for LocationsPanel {
-    fn handle_input(&mut self, key_event: KeyEvent) -> Option<Action> {
-        match key_event.code {
-            event::KeyCode::Char('j') => self.state.select_next(),
-            event::KeyCode::Char('k') => self.state.select_previous(),
-            event::KeyCode::Char('D') => {
-                let selected_location_id = self.state.selected().unwrap();
-                let element = self.locations.remove(selected_location_id);
-                return Some(Action::DeleteLocation(element.name));
+    fn key_context(&self) -> KeyContext {
+        KeyContext::Locations
+    }
+
+    fn handle_command(&mut self, command: Command, count: u32) -> Option<Action> {
+        match command {
+            Command::SelectNext => self
+                .state
+                .scroll_down_by(count.try_into().unwrap_or(u16::MAX)),
+            Command::SelectPrev => self
+                .state
+                .scroll_up_by(count.try_into().unwrap_or(u16::MAX)),
+            Command::SelectFirst => self.state.select_first(),
+            Command::SelectLast => self.state.select_last(),
+            Command::DeleteLocation => {
+                let location = self.selected()?;
+                return Some(Action::DeleteLocation(location.name.clone()));
+            }
+            Command::EditLocation => {
+                let location = self.selected()?;
+                return Some(Action::EditLocation(location.name.clone()));
             }
-            event::KeyCode::Char('A') => {
+            Command::ArchiveLocation => {
+                let location = self.selected()?;
+                return Some(Action::ArchiveLocation(
+                    location.name.clone(),
+                    !location.archived,
+                ));
+            }
+            Command::ShowArchived => {
+                let name = self.selected().map(|l| l.name.clone());
+                self.show_archived = !self.show_archived;
+                self.reselect(name);
+            }
+            Command::Undo => return Some(Action::UndoDeleteLocation),
+            Command::AddLocation => {
                 return Some(Action::AddLocation(LocationModalState::default()));
             }
             _ => return None,
@@ -50,29 +103,48 @@ impl Panel for LocationsPanel {
     }
 
     fn update(&mut self, action: &Action) {
-        if let Action::AddLocationDbSuccess(locations) = action {
+        if let Action::AddLocationDbSuccess(locations)
+        | Action::DeleteLocationDbSuccess(locations)
+        | Action::ArchiveLocationDbSuccess(locations)
+        | Action::EditLocationDbSuccess(locations) = action
+        {
+            let name = self.selected().map(|l| l.name.clone());
             self.locations = locations.clone();
+            self.reselect(name);
         }
     }
     fn render(&mut self, frame: &mut Frame, area: ratatui::layout::Rect, focussed: bool) {
         let label = Span::raw(self.label.clone());
-        let tag_style = Style::default().fg(ratatui::style::Color::LightRed);
+        let tag_style = Style::default().fg(config().focus_color);
         let tagspan = Span::raw(&self.tag).style(tag_style.bold());
 
-        let title = Line::raw("").spans([tagspan, label]);
+        let mut title = Line::raw("").spans([tagspan, label]);
+        if self.show_archived {
+            title.push_span(Span::raw(" (all)").dark_gray());
+        }
         let mut block = Block::bordered().title(title);
         let block_inner = block.inner(area);
 
-        let items: Vec<String> = self.locations.iter().map(|l| l.name.clone()).collect();
+        let items: Vec<ListItem> = self
+            .visible()
+            .iter()
+            .map(|l| {
+                if l.archived {
+                    ListItem::new(format!("{} (archived)", l.name)).dark_gray()
+                } else {
+                    ListItem::new(l.name.clone())
+                }
+            })
+            .collect();
         let mut list = List::new(items)
             .highlight_style(Style::new().reversed())
             .repeat_highlight_symbol(true);
 
         if focussed {
-            block = block.border_style(Style::default().fg(ratatui::style::Color::LightRed));
+            block = block.border_style(Style::default().fg(config().focus_color));
             list = list.highlight_style(
                 Style::new()
-                    .fg(ratatui::style::Color::LightRed)
+                    .fg(config().focus_color)
                     .add_modifier(Modifier::BOLD),
             );
 
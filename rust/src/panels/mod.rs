@@ -1,12 +1,19 @@
 // pub mod actions;
 pub mod calendar_panel;
+pub mod command_line;
+pub mod confirm_modal;
+pub mod day_colors;
 pub mod debug_panel;
+pub mod help_modal;
 pub mod list_picker;
 pub mod location_modal;
 pub mod location_panel;
 pub mod modal;
+pub mod month_grid;
 pub mod panel;
 pub mod record_modal;
+pub mod status_bar;
+pub mod status_panel;
 pub mod textfield_component;
 
 pub use panel::{Action, Panel, PanelType};
@@ -1,8 +1,11 @@
-use crate::panels::location_modal::AddLocationModal;
+use crate::panels::confirm_modal::ConfirmModal;
+use crate::panels::location_modal::{AddLocationModal, EditLocationModal};
 use crate::panels::record_modal::AddRecordModal;
 
 pub enum ActiveModal {
     None,
     AddLocation(AddLocationModal),
+    EditLocation(EditLocationModal),
     AddRecord(AddRecordModal),
+    Confirm(ConfirmModal),
 }
@@ -0,0 +1,188 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Widget, calendar::CalendarEventStore},
+};
+use time::{Date, Weekday};
+
+use crate::dates::{month_bounds, week_start};
+
+/// Shown behind the number of a marked day
+const MARKER: &str = "•";
+
+/// A month calendar like ratatui's `Monthly`, but starting the week on any weekday
+pub struct MonthGrid<'a> {
+    month: Date,
+    events: &'a CalendarEventStore,
+    first_weekday: Weekday,
+    default_style: Style,
+    month_header: Option<Style>,
+    weekdays_header: Option<Style>,
+    surrounding: Option<Style>,
+    /// Days with a marker behind their number, e.g. the days with a note
+    marked: Vec<Date>,
+}
+
+impl<'a> MonthGrid<'a> {
+    /// A grid for the month containing `month`
+    pub fn new(month: Date, events: &'a CalendarEventStore, first_weekday: Weekday) -> Self {
+        MonthGrid {
+            month,
+            events,
+            first_weekday,
+            default_style: Style::default(),
+            month_header: None,
+            weekdays_header: None,
+            surrounding: None,
+            marked: Vec::new(),
+        }
+    }
+
+    pub fn show_month_header(mut self, style: Style) -> Self {
+        self.month_header = Some(style);
+        self
+    }
+
+    pub fn show_weekdays_header(mut self, style: Style) -> Self {
+        self.weekdays_header = Some(style);
+        self
+    }
+
+    /// Show the days of the previous and next month in the first and last week
+    pub fn show_surrounding(mut self, style: Style) -> Self {
+        self.surrounding = Some(style);
+        self
+    }
+
+    /// Put a marker behind the number of these days
+    pub fn mark_days(mut self, days: Vec<Date>) -> Self {
+        self.marked = days;
+        self
+    }
+
+    /// Width of the grid, 7 days of 3 characters and the marker of the last column
+    pub const WIDTH: u16 = 22;
+
+    /// The first day shown in the grid
+    pub fn first_shown(&self) -> Date {
+        let (first, _) = month_bounds(self.month);
+        week_start(first, self.first_weekday)
+    }
+
+    fn day_style(&self, day: Date) -> Style {
+        let base = if day.month() == self.month.month() {
+            self.default_style
+        } else {
+            self.surrounding.unwrap_or_default()
+        };
+        match self.events.0.get(&day) {
+            Some(style) => base.patch(*style),
+            None => base,
+        }
+    }
+}
+
+impl Widget for MonthGrid<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut lines = Vec::new();
+        if let Some(style) = self.month_header {
+            let title = format!("{} {}", self.month.month(), self.month.year());
+            lines.push(Line::styled(title, style).centered());
+        }
+        if let Some(style) = self.weekdays_header {
+            let mut weekday = self.first_weekday;
+            let mut header = String::new();
+            for _ in 0..7 {
+                header.push_str(&format!(" {:.2}", weekday.to_string()));
+                weekday = weekday.next();
+            }
+            lines.push(Line::styled(header, style));
+        }
+
+        // no more days after the last supported date
+        let mut day = Some(self.first_shown());
+        for _ in 0..6 {
+            let mut spans = Vec::new();
+            // the space in front of a day holds the marker of the day before it
+            let mut separator = " ";
+            for _ in 0..7 {
+                spans.push(Span::raw(separator));
+                separator = " ";
+                let Some(shown) = day else {
+                    spans.push(Span::raw("  "));
+                    continue;
+                };
+                if self.surrounding.is_some() || shown.month() == self.month.month() {
+                    spans.push(Span::styled(
+                        format!("{:>2}", shown.day()),
+                        self.day_style(shown),
+                    ));
+                    if self.marked.contains(&shown) {
+                        separator = MARKER;
+                    }
+                } else {
+                    spans.push(Span::raw("  "));
+                }
+                day = shown.next_day();
+            }
+            spans.push(Span::raw(separator));
+            lines.push(Line::from(spans));
+        }
+
+        for (line, y) in lines.into_iter().zip(area.top()..area.bottom()) {
+            let row = Rect::new(area.x, y, area.width.min(Self::WIDTH), 1);
+            line.render(row, buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::date;
+
+    use super::*;
+
+    fn first_shown(month: Date, first_weekday: Weekday) -> Date {
+        let events = CalendarEventStore::default();
+        MonthGrid::new(month, &events, first_weekday).first_shown()
+    }
+
+    #[test]
+    fn first_shown_starts_the_week_on_the_first_weekday() {
+        // October 2026 starts on a Thursday
+        let october = date!(2026 - 10 - 18);
+        assert_eq!(first_shown(october, Weekday::Monday), date!(2026 - 09 - 28));
+        assert_eq!(first_shown(october, Weekday::Sunday), date!(2026 - 09 - 27));
+        assert_eq!(
+            first_shown(october, Weekday::Saturday),
+            date!(2026 - 09 - 26)
+        );
+        assert_eq!(
+            first_shown(october, Weekday::Thursday),
+            date!(2026 - 10 - 01)
+        );
+        assert_eq!(first_shown(october, Weekday::Friday), date!(2026 - 09 - 25));
+    }
+
+    #[test]
+    fn renders_the_weeks_from_the_first_weekday() {
+        let events = CalendarEventStore::default();
+        let grid = MonthGrid::new(date!(2026 - 02 - 01), &events, Weekday::Sunday)
+            .show_weekdays_header(Style::default());
+        let area = Rect::new(0, 0, MonthGrid::WIDTH, 7);
+        let mut buf = Buffer::empty(area);
+        grid.render(area, &mut buf);
+        let row = |y: u16| {
+            (0..area.width)
+                .map(|x| buf[(x, y)].symbol())
+                .collect::<String>()
+        };
+        assert_eq!(row(0), " Su Mo Tu We Th Fr Sa ");
+        // February 2026 starts on a Sunday
+        assert_eq!(row(1), "  1  2  3  4  5  6  7 ");
+        assert_eq!(row(4), " 22 23 24 25 26 27 28 ");
+        assert_eq!(row(5).trim(), "");
+    }
+}
@@ -1,11 +1,13 @@
-use crossterm::event::KeyEvent;
+use crate::config::config;
 use ratatui::{Frame, style::Style, text::Span, widgets::Block};
 use time::Date;
 
 use crate::{
+    keymap::{Command, KeyContext},
+    line_command::LineCommand,
     panels::{
-        location_modal::LocationModalState,
-        record_modal::{AddRecordModal, RecordModalData},
+        calendar_panel::CalendarView, location_modal::LocationModalState,
+        record_modal::RecordModalData, status_bar::Notification,
     },
     store::{Location, Record},
 };
@@ -22,27 +24,70 @@ pub trait Panel {
         let inner = block.inner(area);
         let span = Span::raw("this is a default span");
         if focussed {
-            block = block.border_style(Style::default().fg(ratatui::style::Color::LightRed));
+            block = block.border_style(Style::default().fg(config().focus_color));
         }
         frame.render_widget(block, area);
         frame.render_widget(span, inner);
     }
-    fn handle_input(&mut self, key: KeyEvent) -> Option<Action>;
+    /// The context whose key bindings apply while the panel is focussed
+    fn key_context(&self) -> KeyContext;
+    /// React to a command bound in the key context, `count` is 1 unless a count was typed
+    fn handle_command(&mut self, command: Command, count: u32) -> Option<Action>;
     fn update(&mut self, _action: &Action) {}
+    /// Whether the panel takes the whole screen while it is focussed
+    fn maximized(&self) -> bool {
+        false
+    }
 }
 #[derive(Eq, PartialEq)]
 pub enum Action {
     None,
     CancelModal,
+    /// Show the key bindings of these contexts
+    ShowHelp(Vec<KeyContext>),
+    CloseHelp,
+    /// Open the command line with the start of a command already typed
+    OpenCommandLine(String),
+    RunCommand(LineCommand),
+    Notify(Notification),
+    DismissNotifications,
     AddLocation(LocationModalState),
     ConfirmAddLocation(String),
+    /// Ask before deleting a location and its records
     DeleteLocation(String),
+    ConfirmDeleteLocation(String),
+    /// A deleted location with its records, kept to undo the deletion
+    LocationDeleted(Location, Vec<Record>),
+    UndoDeleteLocation,
+    /// Open the edit modal of the named location
+    EditLocation(String),
+    /// Save the new name and tag of a location
+    ConfirmEditLocation(Location),
+    /// Archive the named location, or unarchive it when the flag is false
+    ArchiveLocation(String, bool),
     AddLocationDbSuccess(Vec<Location>),
     DeleteLocationDbSuccess(Vec<Location>),
+    ArchiveLocationDbSuccess(Vec<Location>),
+    EditLocationDbSuccess(Vec<Location>),
 
     StartNavigateDate(Date),
-    LoadNavigateDateSuccess(Vec<Record>),
+    /// The calendar switched views, the records it shows may have to be loaded
+    ChangeCalendarView(CalendarView),
+    /// The records between both days, see `records_range`
+    LoadRecordsSuccess((Date, Date), Vec<Record>),
+    /// Load the records around the selected date again
+    ReloadRecords,
     AddRecord(RecordModalData),
+    ConfirmAddRecord(RecordModalData),
+    ReplaceRecord(RecordModalData),
+    AddRecordDbSuccess(Record),
+    /// Ask before deleting the record of a day
+    DeleteRecord(Date),
+    ConfirmDeleteRecord(Date),
+    /// A deleted record, kept to undo the deletion
+    RecordDeleted(Record),
+    UndoDeleteRecord,
+    DeleteRecordDbSuccess(Date),
     Processing,
     Skipped,
     QuitApplication,
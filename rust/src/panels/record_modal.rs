@@ -1,108 +1,257 @@
 use crate::{
+    config::config,
+    dates::{display_date, parse_display_date},
+    keymap::{Command, KeyContext},
     panels::{
-        list_picker::SimpleListPicker, list_picker::SimpleListPickerResult,
-        textfield_component::TextField,
+        list_picker::SimpleListPicker,
+        list_picker::SimpleListPickerResult,
+        textfield_component::{TextField, TextFieldResult},
     },
-    store::{Location, LogType},
+    store::{Location, LogType, Record},
 };
 use crossterm::event::KeyEvent;
 use ratatui::{
     Frame,
     layout::{Constraint, Layout, Rect},
-    widgets::{Block, Clear, ListState, Paragraph},
+    style::{Style, Stylize},
+    widgets::{Block, Clear, Paragraph, Wrap},
 };
 use time::Date;
 
-#[derive(Eq, PartialEq)]
+#[derive(Clone, Eq, PartialEq)]
 /// The value provided by the Record Modal
 pub struct RecordModalData {
     pub date: Date,
     pub location: String,
     pub log_type: LogType,
+    pub note: Option<String>,
 }
 
 pub enum RecordModalResult {
     Confirmed(RecordModalData),
+    /// Confirmed after agreeing to overwrite the record already logged that day
+    ConfirmedReplace(RecordModalData),
     Cancelled,
+    /// The typed in value can't be used, the message tells why
+    Invalid(String),
 }
 enum Dialog {
     None,
+    ConfirmReplace(String),
+    EditDate,
+    EditNote,
     PickLocation(SimpleListPicker),
+    PickLogType(SimpleListPicker),
 }
 
 pub struct AddRecordModal {
     pub state: RecordModalData,
     date_field: TextField,
+    note_field: TextField,
+    /// The logged day being edited, the modal was opened with its record
+    editing: Option<Date>,
     locations: Vec<Location>,
     active_dialog: Dialog,
-    locations_widget_state: ListState,
 }
 
 impl AddRecordModal {
-    pub fn new(date: Date, locations: Vec<Location>) -> Self {
-        let mut new_instance = Self {
-            state: RecordModalData {
-                date: date,
-                location: "".to_string(),
-                log_type: LogType::Work,
-            },
-            date_field: TextField::new("Record Date".to_string(), Some(date.to_string())),
-            locations: locations,
-            active_dialog: Dialog::None,
-            locations_widget_state: ListState::default(),
+    /// A modal filled in with `data`, an empty location is replaced by the default one
+    pub fn new(data: RecordModalData, locations: Vec<Location>) -> Self {
+        let config = config();
+        let editing = (!data.location.is_empty()).then_some(data.date);
+        let location = if data.location.is_empty() {
+            locations
+                .iter()
+                .filter(|l| !l.archived)
+                .find(|l| Some(&l.name) == config.default_location.as_ref())
+                .or(locations.iter().find(|l| !l.archived))
+                .map(|l| l.name.clone())
+                .unwrap_or_default()
+        } else {
+            data.location
         };
-        new_instance.date_field.state.value = date.to_string();
-        new_instance.locations_widget_state.select_first();
-        new_instance
+        Self {
+            date_field: TextField::new("Record Date".to_string(), Some(display_date(data.date))),
+            note_field: TextField::new("Note".to_string(), data.note.clone()),
+            editing,
+            state: RecordModalData { location, ..data },
+            locations,
+            active_dialog: Dialog::None,
+        }
+    }
+
+    /// Whether saving overwrites the record the modal was opened with, which needs no
+    /// confirmation
+    pub fn edits(&self, date: Date) -> bool {
+        self.editing == Some(date)
+    }
+
+    /// Ask the user whether the record already logged on the selected day should be replaced
+    pub fn ask_replace(&mut self, existing: &Record) {
+        let location_name = self
+            .locations
+            .iter()
+            .find(|l| l.id == existing.location_id)
+            .map(|l| l.name.clone())
+            .unwrap_or("an unknown location".to_string());
+        let question = format!(
+            "{} is already logged at {} ({}). Replace existing record? (y/n)",
+            display_date(existing.date),
+            location_name,
+            existing.log_type
+        );
+        self.active_dialog = Dialog::ConfirmReplace(question);
     }
 
+    /// The context of the key bindings, `None` while the date is typed in
+    pub fn key_context(&self) -> Option<KeyContext> {
+        match self.active_dialog {
+            Dialog::None => Some(KeyContext::RecordModal),
+            Dialog::ConfirmReplace(_) => Some(KeyContext::Prompt),
+            Dialog::PickLocation(_) | Dialog::PickLogType(_) => Some(KeyContext::Picker),
+            Dialog::EditDate | Dialog::EditNote => None,
+        }
+    }
+
+    /// Handle a key while the date or the note is typed in
     pub fn handle_input(&mut self, key_event: KeyEvent) -> Option<RecordModalResult> {
+        if let Dialog::EditNote = self.active_dialog {
+            match self.note_field.handle_input(key_event) {
+                TextFieldResult::Editting => (),
+                TextFieldResult::Cancelled => {
+                    self.note_field.state.value = self.state.note.clone().unwrap_or_default();
+                    self.active_dialog = Dialog::None;
+                }
+                TextFieldResult::Confirm(state) => {
+                    let note = state.value.trim();
+                    self.state.note = (!note.is_empty()).then(|| note.to_string());
+                    self.active_dialog = Dialog::None;
+                }
+            }
+        }
+        if let Dialog::EditDate = self.active_dialog {
+            match self.date_field.handle_input(key_event) {
+                TextFieldResult::Editting => (),
+                TextFieldResult::Cancelled => {
+                    self.reset_date_field();
+                    self.active_dialog = Dialog::None;
+                }
+                TextFieldResult::Confirm(state) => {
+                    // an invalid date stays in the field to be corrected
+                    let Some(date) = parse_display_date(&state.value) else {
+                        return Some(RecordModalResult::Invalid(format!(
+                            "'{}' is not a date, expected e.g. {}",
+                            state.value.trim(),
+                            display_date(self.state.date)
+                        )));
+                    };
+                    self.state.date = date;
+                    self.reset_date_field();
+                    self.active_dialog = Dialog::None;
+                }
+            }
+        }
+        None
+    }
+
+    pub fn handle_command(&mut self, command: Command, count: u32) -> Option<RecordModalResult> {
         match &mut self.active_dialog {
+            Dialog::ConfirmReplace(_) => {
+                match command {
+                    Command::Confirm => {
+                        self.active_dialog = Dialog::None;
+                        return Some(RecordModalResult::ConfirmedReplace(self.state.clone()));
+                    }
+                    Command::Cancel => self.active_dialog = Dialog::None,
+                    _ => (),
+                }
+                return None;
+            }
+            Dialog::EditDate | Dialog::EditNote => return None,
             Dialog::PickLocation(picker) => {
-                let list_res = picker.handle_input(key_event);
-                match list_res {
+                match picker.handle_command(command, count) {
                     SimpleListPickerResult::Cancelled => self.active_dialog = Dialog::None,
                     SimpleListPickerResult::Editting => (),
-                    SimpleListPickerResult::Confirmed(choice) => todo!(),
+                    SimpleListPickerResult::Confirmed(choice) => {
+                        self.state.location = choice;
+                        self.active_dialog = Dialog::None;
+                    }
+                }
+                return None;
+            }
+            Dialog::PickLogType(picker) => {
+                match picker.handle_command(command, count) {
+                    SimpleListPickerResult::Cancelled => self.active_dialog = Dialog::None,
+                    SimpleListPickerResult::Editting => (),
+                    SimpleListPickerResult::Confirmed(choice) => {
+                        if let Ok(log_type) = choice.parse() {
+                            self.state.log_type = log_type;
+                        }
+                        self.active_dialog = Dialog::None;
+                    }
                 }
                 return None;
             }
             Dialog::None => (),
         };
-        match key_event.code.as_char() {
-            Some(_c) => match _c {
-                // setup location
-                't' => todo!(),
-
-                // setup location
-                'l' => {
-                    let location_names = self.locations.iter().map(|l| l.name.clone()).collect();
-                    let mut picker = SimpleListPicker::new(location_names, None);
-                    picker.select_default();
-                    self.active_dialog = Dialog::PickLocation(picker)
+        match command {
+            // setup date
+            Command::EditDate => {
+                self.date_field.move_cursor_end();
+                self.active_dialog = Dialog::EditDate;
+            }
+
+            Command::EditNote => {
+                self.note_field.move_cursor_end();
+                self.active_dialog = Dialog::EditNote;
+            }
+
+            // setup log type
+            Command::PickType => {
+                let mut picker =
+                    SimpleListPicker::new(LogType::names(), Some(self.state.log_type.to_string()));
+                picker.select_default();
+                self.active_dialog = Dialog::PickLogType(picker)
+            }
+
+            // setup location
+            Command::PickLocation => {
+                // archived locations can't be picked for new records
+                let location_names: Vec<String> = self
+                    .locations
+                    .iter()
+                    .filter(|l| !l.archived)
+                    .map(|l| l.name.clone())
+                    .collect();
+                if location_names.is_empty() {
+                    return None;
                 }
+                let mut picker =
+                    SimpleListPicker::new(location_names, Some(self.state.location.clone()));
+                picker.select_default();
+                self.active_dialog = Dialog::PickLocation(picker)
+            }
 
-                // setup location
-                'q' => return Some(RecordModalResult::Cancelled),
-
-                // // dialog down
-                // 'j' => self.locations_widget_state.select_next(),
-                //
-                // // dialog up
-                // 'k' => self.locations_widget_state.select_previous(),
-                _ => return None,
-            },
-            None => (),
-        };
-        if key_event.code.is_esc() {
-            return Some(RecordModalResult::Cancelled);
+            Command::Cancel => return Some(RecordModalResult::Cancelled),
+            Command::Confirm if !self.state.location.is_empty() => {
+                return Some(RecordModalResult::Confirmed(self.state.clone()));
+            }
+            _ => (),
         }
         None
     }
 
+    fn reset_date_field(&mut self) {
+        self.date_field.state.value = display_date(self.state.date);
+    }
+
     pub fn render(&mut self, frame: &mut Frame, area: Rect) {
-        let page = Block::bordered().title("Add New Log");
-        // let chunks = horizontal.split(page.inner(area));
+        let title = if self.editing.is_some() {
+            "Edit Log"
+        } else {
+            "Add New Log"
+        };
+        let page = Block::bordered().title(title);
         let inner_area = page.inner(area);
         let chunks = Layout::default()
             .direction(ratatui::layout::Direction::Vertical)
@@ -110,34 +259,76 @@ impl AddRecordModal {
                 Constraint::Length(3),
                 Constraint::Length(3),
                 Constraint::Length(3),
+                Constraint::Length(3),
+                Constraint::Length(1),
             ])
             .split(inner_area);
 
         // render main widget
         frame.render_widget(Clear, area);
         frame.render_widget(page, area);
-        // frame.render_widget(input, area);
         // render subcomponents
-        let date_area = *chunks.first().unwrap();
-        let locations_area = &chunks[1];
-        let type_area = &chunks[2];
-        self.date_field.render(frame, date_area);
+        let date_area = chunks[0];
+        let locations_area = chunks[1];
+        let type_area = chunks[2];
+        let note_area = chunks[3];
+        let help_area = chunks[4];
+        if let Dialog::EditDate = self.active_dialog {
+            self.date_field.render_focussed(frame, date_area);
+        } else {
+            self.date_field.render(frame, date_area);
+        }
 
         // locations
-        let loc_name = self.locations.first().unwrap().name.clone();
+        let loc_name = if self.state.location.is_empty() {
+            "<no locations, add one first>".to_string()
+        } else {
+            self.state.location.clone()
+        };
         let location_paragraph =
             Paragraph::new(loc_name).block(Block::bordered().title("Location"));
-        frame.render_widget(&location_paragraph, *locations_area);
+        frame.render_widget(&location_paragraph, locations_area);
 
         // types
-        let log_type_name = LogType::names().first().unwrap().clone();
-        let type_widget =
-            Paragraph::new(log_type_name).block(Block::bordered().title("Type".to_string()));
-        frame.render_widget(type_widget, *type_area);
+        let type_widget = Paragraph::new(self.state.log_type.to_string())
+            .block(Block::bordered().title("Type".to_string()));
+        frame.render_widget(type_widget, type_area);
+
+        if let Dialog::EditNote = self.active_dialog {
+            self.note_field.render_focussed(frame, note_area);
+        } else {
+            self.note_field.render(frame, note_area);
+        }
+
+        let keymap = &config().keymap;
+        let help: Vec<String> = [
+            (Command::EditDate, "date"),
+            (Command::PickLocation, "location"),
+            (Command::PickType, "type"),
+            (Command::EditNote, "note"),
+            (Command::Confirm, "save"),
+            (Command::Cancel, "cancel"),
+            (Command::Help, "help"),
+        ]
+        .iter()
+        .filter_map(|(command, label)| {
+            let key = keymap.key_for(KeyContext::RecordModal, *command)?;
+            Some(format!("{key}: {label}"))
+        })
+        .collect();
+        let help = Paragraph::new(help.join("  ")).style(Style::default().dark_gray());
+        frame.render_widget(help, help_area);
 
         match &mut self.active_dialog {
-            Dialog::None => {}
-            Dialog::PickLocation(picker) => {
+            Dialog::None | Dialog::EditDate | Dialog::EditNote => {}
+            Dialog::ConfirmReplace(question) => {
+                let prompt = Paragraph::new(question.as_str())
+                    .wrap(Wrap { trim: true })
+                    .block(Block::bordered().title("Replace Record"));
+                frame.render_widget(Clear, inner_area);
+                frame.render_widget(prompt, inner_area);
+            }
+            Dialog::PickLocation(picker) | Dialog::PickLogType(picker) => {
                 picker.render(frame, inner_area);
             }
         }
@@ -0,0 +1,170 @@
+use std::time::{Duration, Instant};
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Margin, Rect},
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{Block, Clear, Paragraph, Wrap},
+};
+
+use crate::{
+    config::config,
+    keymap::{Command, KeyContext},
+};
+
+/// How long a success or warning stays on screen, errors stay until dismissed
+const TOAST_DURATION: Duration = Duration::from_secs(4);
+const TOAST_WIDTH: u16 = 48;
+/// Number of toasts shown at once, the newest ones win
+const MAX_TOASTS: usize = 4;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Level {
+    Success,
+    Warning,
+    Error,
+}
+
+impl Level {
+    fn style(&self) -> Style {
+        match self {
+            Level::Success => Style::default().fg(Color::LightGreen),
+            Level::Warning => Style::default().fg(Color::LightYellow),
+            Level::Error => Style::default().fg(Color::LightRed),
+        }
+    }
+
+    fn title(&self) -> &'static str {
+        match self {
+            Level::Success => "Done",
+            Level::Warning => "Warning",
+            Level::Error => "Error",
+        }
+    }
+}
+
+/// A message for the user, posted through the action channel
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Notification {
+    pub level: Level,
+    pub message: String,
+}
+
+impl Notification {
+    pub fn success(message: impl Into<String>) -> Self {
+        Notification {
+            level: Level::Success,
+            message: message.into(),
+        }
+    }
+
+    pub fn warning(message: impl Into<String>) -> Self {
+        Notification {
+            level: Level::Warning,
+            message: message.into(),
+        }
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Notification {
+            level: Level::Error,
+            message: message.into(),
+        }
+    }
+}
+
+struct Toast {
+    notification: Notification,
+    shown_at: Instant,
+}
+
+/// The bottom line of the screen and the toasts stacked above it
+#[derive(Default)]
+pub struct StatusBar {
+    toasts: Vec<Toast>,
+}
+
+impl StatusBar {
+    pub fn notify(&mut self, notification: Notification) {
+        self.toasts.push(Toast {
+            notification,
+            shown_at: Instant::now(),
+        });
+    }
+
+    /// Remove every toast, including the errors
+    pub fn dismiss(&mut self) {
+        self.toasts.clear();
+    }
+
+    fn expire(&mut self) {
+        self.toasts.retain(|toast| {
+            toast.notification.level == Level::Error || toast.shown_at.elapsed() < TOAST_DURATION
+        });
+    }
+
+    fn has_errors(&self) -> bool {
+        self.toasts
+            .iter()
+            .any(|toast| toast.notification.level == Level::Error)
+    }
+
+    /// The status line, with the keys typed so far on the right
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, pending_keys: &str) {
+        self.expire();
+        let keymap = &config().keymap;
+        let mut hints = vec![
+            (Command::OpenCommandLine, "command"),
+            (Command::Help, "help"),
+        ];
+        if self.has_errors() {
+            hints.push((Command::Dismiss, "dismiss"));
+        }
+        let hints: Vec<String> = hints
+            .iter()
+            .filter_map(|(command, label)| {
+                let key = keymap.key_for(KeyContext::Global, *command)?;
+                Some(format!("{key}: {label}"))
+            })
+            .collect();
+
+        let [hints_area, keys_area] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Length(12)]).areas(area);
+        frame.render_widget(Line::raw(hints.join("  ")).dark_gray(), hints_area);
+        frame.render_widget(Line::raw(pending_keys).right_aligned(), keys_area);
+    }
+
+    /// The toasts in the bottom right corner of `area`, the newest at the bottom
+    pub fn render_toasts(&mut self, frame: &mut Frame, area: Rect) {
+        self.expire();
+        let area = area.inner(Margin::new(1, 1));
+        let width = TOAST_WIDTH.min(area.width);
+        let mut bottom = area.bottom();
+        for toast in self.toasts.iter().rev().take(MAX_TOASTS) {
+            let level = toast.notification.level;
+            let text_width = usize::from(width.saturating_sub(2)).max(1);
+            let lines = toast
+                .notification
+                .message
+                .chars()
+                .count()
+                .div_ceil(text_width);
+            let height = (lines.max(1) as u16 + 2).min(bottom.saturating_sub(area.y));
+            if height < 3 {
+                break;
+            }
+            bottom -= height;
+            let rect = Rect::new(area.right() - width, bottom, width, height);
+            let paragraph = Paragraph::new(toast.notification.message.as_str())
+                .wrap(Wrap { trim: true })
+                .block(
+                    Block::bordered()
+                        .title(level.title())
+                        .border_style(level.style()),
+                );
+            frame.render_widget(Clear, rect);
+            frame.render_widget(paragraph, rect);
+        }
+    }
+}
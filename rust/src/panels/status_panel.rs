@@ -0,0 +1,139 @@
+use ratatui::{
+    Frame,
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+};
+use time::Date;
+
+use crate::{
+    config::config,
+    dates::{month_bounds, today},
+    keymap::{Command, KeyContext},
+    panels::{Action, Panel},
+    stats::Stats,
+    store::{Category, Location, Record},
+};
+
+pub struct StatusPanel {
+    pub label: String,
+    pub tag: String,
+    pub records: Vec<Record>,
+    pub locations: Vec<Location>,
+    /// Any day of the month the stats are shown for
+    pub month: Date,
+}
+
+impl StatusPanel {
+    pub async fn new(locations: Vec<Location>) -> Self {
+        StatusPanel {
+            label: "Statistics".to_string(),
+            tag: " ³".to_string(),
+            records: Vec::new(),
+            locations,
+            month: today(),
+        }
+    }
+}
+
+impl Panel for StatusPanel {
+    fn key_context(&self) -> KeyContext {
+        KeyContext::Status
+    }
+
+    fn handle_command(&mut self, _command: Command, _count: u32) -> Option<Action> {
+        None
+    }
+
+    fn update(&mut self, action: &Action) {
+        match action {
+            Action::StartNavigateDate(date) => self.month = *date,
+            Action::LoadRecordsSuccess(_, records) => self.records = records.clone(),
+            Action::AddRecordDbSuccess(record) => {
+                self.records.retain(|r| r.date != record.date);
+                self.records.push(record.clone());
+            }
+            Action::DeleteRecordDbSuccess(date) => self.records.retain(|r| r.date != *date),
+            Action::AddLocationDbSuccess(locations)
+            | Action::DeleteLocationDbSuccess(locations)
+            | Action::ArchiveLocationDbSuccess(locations)
+            | Action::EditLocationDbSuccess(locations) => {
+                self.locations = locations.clone();
+            }
+            _ => {}
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: ratatui::layout::Rect, focussed: bool) {
+        let label = Span::raw(self.label.clone());
+        let tag_style = Style::default().fg(config().focus_color);
+        let tagspan = Span::raw(&self.tag).style(tag_style.bold());
+
+        let title = Line::raw("").spans([tagspan, label]);
+        let mut block = Block::bordered().title(title);
+        let block_inner = block.inner(area);
+
+        if focussed {
+            block = block.border_style(Style::default().fg(config().focus_color));
+        }
+
+        let (first, last) = month_bounds(self.month);
+        let stats = Stats::new(&self.records, &self.locations, first, last);
+        let header = Style::default().bold();
+
+        let mut lines = vec![Line::styled("Distribution", header)];
+        if stats.locations.is_empty() {
+            lines.push(Line::raw("  no work days logged").dark_gray());
+        }
+        for location in &stats.locations {
+            lines.push(Line::raw(format!(
+                "  {:<16} {:>3} {:>6.1}%",
+                location.name, location.days, location.percentage
+            )));
+        }
+        // only shown once locations have a category
+        let categorized: Vec<_> = stats
+            .categories
+            .iter()
+            .filter(|c| c.category != Category::Unknown)
+            .collect();
+        if !categorized.is_empty() {
+            lines.push(Line::raw(""));
+            lines.push(Line::styled("Categories", header));
+            for category in categorized {
+                lines.push(Line::raw(format!(
+                    "  {:<16} {:>3} {:>6.1}%",
+                    category.category.to_string(),
+                    category.days,
+                    category.percentage
+                )));
+            }
+            if let Some(home) = stats.home_share() {
+                lines.push(Line::raw(format!(
+                    "  Home / office  {:>3.0}% / {:.0}%",
+                    home,
+                    100.0 - home
+                )));
+            }
+        }
+        lines.push(Line::raw(""));
+        lines.push(Line::styled("Workdays", header));
+        lines.push(Line::raw(format!("  This month {:>3}", stats.workdays)));
+        lines.push(Line::raw(format!(
+            "  Logged     {:>3}",
+            stats.logged_workdays
+        )));
+        lines.push(Line::raw(format!(
+            "  Unlogged   {:>3}",
+            stats.unlogged_workdays()
+        )));
+        lines.push(Line::raw(""));
+        lines.push(Line::styled("Days off", header));
+        lines.push(Line::raw(format!("  Holiday    {:>3}", stats.holidays)));
+        lines.push(Line::raw(format!("  Vacation   {:>3}", stats.vacation)));
+        lines.push(Line::raw(format!("  Sick       {:>3}", stats.sick)));
+
+        frame.render_widget(block, area);
+        frame.render_widget(Paragraph::new(lines), block_inner);
+    }
+}
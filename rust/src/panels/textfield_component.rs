@@ -1,10 +1,13 @@
 use crossterm::event::KeyEvent;
 use ratatui::{
     Frame,
-    layout::Rect,
+    layout::{Position, Rect},
+    style::Style,
     widgets::{Block, Clear, Paragraph},
 };
 
+use crate::config::config;
+
 #[derive(Default, Clone)]
 pub struct TextFieldState {
     pub value: String,
@@ -33,7 +36,7 @@ impl TextField {
         Self {
             state: TextFieldState::new(initial_value),
             character_index: 0,
-            label: label,
+            label,
         }
     }
     pub fn clear(&mut self) {
@@ -49,13 +52,15 @@ impl TextField {
         self.character_index = self.clamp_cursor(cursor_moved_right);
     }
 
+    /// Place the cursor after the last character
+    pub fn move_cursor_end(&mut self) {
+        self.character_index = self.state.value.chars().count();
+    }
+
     pub fn handle_input(&mut self, key_event: KeyEvent) -> TextFieldResult {
-        match key_event.code.as_char() {
-            Some(c) => {
-                self.enter_char(c);
-                return TextFieldResult::Editting;
-            }
-            None => (),
+        if let Some(c) = key_event.code.as_char() {
+            self.enter_char(c);
+            return TextFieldResult::Editting;
         }
 
         if key_event.code.is_backspace() {
@@ -117,4 +122,17 @@ impl TextField {
         frame.render_widget(Clear, area);
         frame.render_widget(input, area);
     }
+
+    /// Render the field with a highlighted border and the cursor in it
+    pub fn render_focussed(&self, frame: &mut Frame, area: Rect) {
+        let input = Paragraph::new(self.state.value.as_str()).block(
+            Block::bordered()
+                .title(self.label.clone())
+                .border_style(Style::default().fg(config().focus_color)),
+        );
+        frame.render_widget(Clear, area);
+        frame.render_widget(input, area);
+        let cursor = self.character_index as u16;
+        frame.set_cursor_position(Position::new(area.x + 1 + cursor, area.y + 1));
+    }
 }
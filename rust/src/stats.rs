@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+
+use time::Date;
+
+use crate::{
+    config::config,
+    store::{Category, Location, LogType, Record},
+};
+
+/// Days worked at a single location
+pub struct LocationCount {
+    pub name: String,
+    pub days: usize,
+    pub percentage: f64,
+}
+
+/// Days worked at locations of a single category
+pub struct CategoryCount {
+    pub category: Category,
+    pub days: usize,
+    pub percentage: f64,
+}
+
+/// Distribution of the records logged in a date range
+pub struct Stats {
+    /// Worked days per location, most visited first
+    pub locations: Vec<LocationCount>,
+    /// Worked days per category, in the order of `Category::ALL`, without empty categories
+    pub categories: Vec<CategoryCount>,
+    pub workdays: usize,
+    pub logged_workdays: usize,
+    pub holidays: usize,
+    pub vacation: usize,
+    pub sick: usize,
+}
+
+/// Whether a date falls on one of the configured workdays
+pub fn is_workday(date: Date) -> bool {
+    config().is_workday(date.weekday())
+}
+
+impl Stats {
+    /// Compute the stats of the records between `first` and `last`, both inclusive
+    pub fn new(records: &[Record], locations: &[Location], first: Date, last: Date) -> Self {
+        let in_range: Vec<&Record> = records
+            .iter()
+            .filter(|r| r.date >= first && r.date <= last)
+            .collect();
+
+        let mut workdays = 0;
+        let mut day = Some(first);
+        while let Some(current) = day
+            && current <= last
+        {
+            if is_workday(current) {
+                workdays += 1;
+            }
+            day = current.next_day();
+        }
+
+        let count_type =
+            |log_type: LogType| in_range.iter().filter(|r| r.log_type == log_type).count();
+
+        let mut per_location: HashMap<i64, usize> = HashMap::new();
+        for record in in_range.iter().filter(|r| r.log_type == LogType::Work) {
+            *per_location.entry(record.location_id).or_default() += 1;
+        }
+        let worked: usize = per_location.values().sum();
+        let category_of = |location_id: i64| {
+            locations
+                .iter()
+                .find(|l| l.id == location_id)
+                .map(|l| l.category)
+                .unwrap_or(Category::Unknown)
+        };
+        let categories = Category::ALL
+            .iter()
+            .map(|category| {
+                let days = per_location
+                    .iter()
+                    .filter(|(location_id, _)| category_of(**location_id) == *category)
+                    .map(|(_, days)| days)
+                    .sum();
+                CategoryCount {
+                    category: *category,
+                    days,
+                    percentage: days as f64 / worked as f64 * 100.0,
+                }
+            })
+            .filter(|count| count.days > 0)
+            .collect();
+        let mut location_counts: Vec<LocationCount> = per_location
+            .into_iter()
+            .map(|(location_id, days)| LocationCount {
+                name: locations
+                    .iter()
+                    .find(|l| l.id == location_id)
+                    .map(|l| l.name.clone())
+                    .unwrap_or("unknown".to_string()),
+                days,
+                percentage: days as f64 / worked as f64 * 100.0,
+            })
+            .collect();
+        location_counts.sort_by(|a, b| b.days.cmp(&a.days).then(a.name.cmp(&b.name)));
+
+        Stats {
+            locations: location_counts,
+            categories,
+            workdays,
+            logged_workdays: in_range.iter().filter(|r| is_workday(r.date)).count(),
+            holidays: count_type(LogType::Holiday),
+            vacation: count_type(LogType::Vacation),
+            sick: count_type(LogType::Sick),
+        }
+    }
+
+    pub fn unlogged_workdays(&self) -> usize {
+        self.workdays.saturating_sub(self.logged_workdays)
+    }
+
+    pub fn category_days(&self, category: Category) -> usize {
+        self.categories
+            .iter()
+            .find(|c| c.category == category)
+            .map_or(0, |c| c.days)
+    }
+
+    /// Percentage of the days worked at home or at the office that were spent at home,
+    /// `None` when neither was logged
+    pub fn home_share(&self) -> Option<f64> {
+        let home = self.category_days(Category::Home);
+        let office = self.category_days(Category::Office);
+        if home + office == 0 {
+            return None;
+        }
+        Some(home as f64 / (home + office) as f64 * 100.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::date;
+
+    use super::*;
+
+    fn location(id: i64, name: &str, category: Category) -> Location {
+        Location {
+            id,
+            name: name.to_string(),
+            tag: String::new(),
+            archived: false,
+            category,
+        }
+    }
+
+    fn record(date: Date, location_id: i64, log_type: LogType) -> Record {
+        Record {
+            id: 0,
+            location_id,
+            date,
+            log_type,
+            note: None,
+        }
+    }
+
+    #[test]
+    fn counts_the_days_of_a_month() {
+        let locations = [
+            location(1, "Home", Category::Home),
+            location(2, "New York", Category::Office),
+        ];
+        let records = [
+            // the day before the month
+            record(date!(2026 - 09 - 30), 2, LogType::Work),
+            record(date!(2026 - 10 - 07), 1, LogType::Work),
+            record(date!(2026 - 10 - 08), 2, LogType::Work),
+            record(date!(2026 - 10 - 09), 1, LogType::Work),
+            // a Saturday
+            record(date!(2026 - 10 - 10), 1, LogType::Work),
+            record(date!(2026 - 10 - 12), 1, LogType::Work),
+            record(date!(2026 - 10 - 13), 1, LogType::Vacation),
+            // a location that no longer exists
+            record(date!(2026 - 10 - 14), 9, LogType::Work),
+        ];
+        let stats = Stats::new(
+            &records,
+            &locations,
+            date!(2026 - 10 - 01),
+            date!(2026 - 10 - 31),
+        );
+
+        assert_eq!(stats.workdays, 22);
+        assert_eq!(stats.logged_workdays, 6);
+        assert_eq!(stats.unlogged_workdays(), 16);
+        assert_eq!(stats.vacation, 1);
+        assert_eq!(stats.holidays, 0);
+
+        let locations: Vec<(&str, usize)> = stats
+            .locations
+            .iter()
+            .map(|l| (l.name.as_str(), l.days))
+            .collect();
+        assert_eq!(locations, [("Home", 4), ("New York", 1), ("unknown", 1)]);
+        assert_eq!(stats.category_days(Category::Home), 4);
+        assert_eq!(stats.category_days(Category::Office), 1);
+        assert_eq!(stats.category_days(Category::Unknown), 1);
+        assert_eq!(stats.home_share(), Some(80.0));
+    }
+
+    #[test]
+    fn counts_without_records() {
+        let stats = Stats::new(&[], &[], date!(2026 - 10 - 12), date!(2026 - 10 - 18));
+        assert_eq!(stats.workdays, 5);
+        assert_eq!(stats.unlogged_workdays(), 5);
+        assert!(stats.locations.is_empty());
+        assert_eq!(stats.home_share(), None);
+    }
+
+    #[test]
+    fn counts_up_to_the_last_supported_day() {
+        let stats = Stats::new(&[], &[], date!(9999 - 12 - 25), Date::MAX);
+        assert_eq!(stats.workdays, 5);
+    }
+}
@@ -1,45 +1,39 @@
-use crate::store::{Location, LogType, Record};
-use color_eyre::Result;
-use sqlx::{SqlitePool, sqlite::SqliteConnectOptions};
-use std::{
-    fs::{self},
-    path::PathBuf,
+use crate::{
+    dates::month_bounds,
+    store::{Category, Location, LogType, NamedRecord, Profile, Record, RecordDetails},
 };
+use color_eyre::{Result, eyre::eyre};
+use sqlx::{SqlitePool, sqlite::SqliteConnectOptions};
+use std::fs::{self};
 use time::Date;
 
 pub struct Store {
     pool: SqlitePool,
 }
-fn get_db_path() -> PathBuf {
-    let dirs = directories::ProjectDirs::from("be", "waystone", "locwork").unwrap();
-    let mut config_dir = dirs.config_dir().to_path_buf();
-    config_dir.push("app.db");
-    // makes config dir ~/.config/locwork/app.db
-    config_dir
-}
 
 impl Store {
-    pub async fn new() -> Result<Self> {
-        // TODO: read  connection_url from DATABASE_URL env var?
-        let parent_folder = get_db_path().parent().unwrap().to_owned();
-        if !fs::exists(&parent_folder).unwrap() {
-            fs::create_dir_all(parent_folder).unwrap();
+    pub async fn new(profile: &Profile) -> Result<Self> {
+        if let Some(parent_folder) = profile.db_path.parent()
+            && !parent_folder.as_os_str().is_empty()
+            && !fs::exists(parent_folder)?
+        {
+            fs::create_dir_all(parent_folder)?;
         }
         let ops = SqliteConnectOptions::new()
-            .filename(get_db_path())
+            .filename(&profile.db_path)
             .create_if_missing(true);
 
         let store = Store {
-            pool: SqlitePool::connect_with(ops).await?,
+            pool: SqlitePool::connect_with(ops)
+                .await
+                .map_err(|e| eyre!("could not open database {}: {e}", profile.db_path.display()))?,
         };
-        sqlx::migrate!("./migrations")
-            .run(&store.pool)
-            .await
-            .unwrap();
+        sqlx::migrate!("./migrations").run(&store.pool).await?;
 
         Ok(store)
     }
 
+    #[allow(clippy::needless_return)]
     pub async fn get_locations(&self) -> Result<Vec<Location>> {
         let rows: Vec<Location> = sqlx::query_as!(
             Location,
@@ -47,7 +41,9 @@ impl Store {
                 SELECT
                 id AS "id!",
                 name AS "name!",
-                tag AS "tag!"
+                tag AS "tag!",
+                archived AS "archived: bool",
+                category AS "category: Category"
                 FROM Location
             "#
         )
@@ -57,6 +53,27 @@ impl Store {
         return Ok(rows);
     }
 
+    pub async fn get_location_by_name(&self, name: &str) -> Result<Option<Location>> {
+        let row: Option<Location> = sqlx::query_as!(
+            Location,
+            r#"
+                SELECT
+                id AS "id!",
+                name AS "name!",
+                tag AS "tag!",
+                archived AS "archived: bool",
+                category AS "category: Category"
+                FROM Location
+                WHERE name = ?
+            "#,
+            name
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row)
+    }
+
     pub async fn delete_location_by_name(&self, name: &str) -> Result<()> {
         let _ = sqlx::query_as!(
             Location,
@@ -71,17 +88,80 @@ impl Store {
         Ok(())
     }
 
-    pub async fn add_location(&self, name: String, tag: Option<String>) -> Result<Location> {
+    /// Hide a location from the pickers, its records stay
+    pub async fn archive_location(&self, name: &str) -> Result<()> {
+        self.set_archived(name, true).await
+    }
+
+    pub async fn unarchive_location(&self, name: &str) -> Result<()> {
+        self.set_archived(name, false).await
+    }
+
+    async fn set_archived(&self, name: &str, archived: bool) -> Result<()> {
+        let result = sqlx::query!(
+            r#"
+                UPDATE Location
+                SET archived = ?
+                WHERE name = ?
+            "#,
+            archived,
+            name
+        )
+        .execute(&self.pool)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(eyre!("location '{name}' is not recognized"));
+        }
+        Ok(())
+    }
+
+    /// Save the name, tag and category of a location, its name must stay unique
+    pub async fn update_location(&self, location: &Location) -> Result<Location> {
+        let row: Option<Location> = sqlx::query_as!(
+            Location,
+            r#"
+                UPDATE Location
+                SET name = ?1, tag = ?2, category = ?4
+                WHERE id = ?3
+                    AND NOT EXISTS (SELECT 1 FROM Location WHERE name = ?1 AND id != ?3)
+                RETURNING id as "id!", name as "name!", tag as "tag!", archived as "archived: bool",
+                    category as "category: Category"
+            "#,
+            location.name,
+            location.tag,
+            location.id,
+            location.category
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+        match row {
+            Some(row) => Ok(row),
+            None if self.get_location_by_name(&location.name).await?.is_some() => {
+                Err(eyre!("location '{}' already exists", location.name))
+            }
+            None => Err(eyre!("location {} does not exist anymore", location.id)),
+        }
+    }
+
+    #[allow(clippy::needless_return)]
+    pub async fn add_location(
+        &self,
+        name: String,
+        tag: Option<String>,
+        category: Category,
+    ) -> Result<Location> {
         let utag = tag.unwrap_or("".to_string());
         let row: Location = sqlx::query_as!(
             Location,
             r#"
-                INSERT INTO Location (name, tag)
-                VALUES (?, ?)
-                RETURNING id as "id!", name as "name!", tag as "tag!"
+                INSERT INTO Location (name, tag, category)
+                VALUES (?, ?, ?)
+                RETURNING id as "id!", name as "name!", tag as "tag!", archived as "archived: bool",
+                    category as "category: Category"
             "#,
             name,
-            utag
+            utag,
+            category
         )
         .fetch_one(&self.pool)
         .await?;
@@ -93,24 +173,275 @@ impl Store {
         date: Date,
         log_type: LogType,
         location: Location,
+        note: Option<String>,
     ) -> Result<Record> {
         let inserted: Record = sqlx::query_as!(
             Record,
             r#"
-            INSERT INTO Record (date, location_id, log_type)
-            VALUES (?, ?, ?)
+            INSERT INTO Record (date, location_id, log_type, note)
+            VALUES (?, ?, ?, ?)
             RETURNING 
                 id as "id!",
                 date as "date: Date", 
                 location_id as "location_id!",
-                log_type as "log_type: LogType"
+                log_type as "log_type: LogType",
+                note
             "#,
             date,
             location.id,
             log_type,
+            note,
         )
         .fetch_one(&self.pool)
         .await?;
         Ok(inserted)
     }
+
+    /// Insert a record, or overwrite the record already logged on that date
+    pub async fn upsert_record(
+        &self,
+        date: Date,
+        log_type: LogType,
+        location: Location,
+        note: Option<String>,
+    ) -> Result<Record> {
+        let upserted: Record = sqlx::query_as!(
+            Record,
+            r#"
+            INSERT INTO Record (date, location_id, log_type, note)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT(date) DO UPDATE SET
+                location_id = excluded.location_id,
+                log_type = excluded.log_type,
+                note = excluded.note
+            RETURNING
+                id as "id!",
+                date as "date: Date",
+                location_id as "location_id!",
+                log_type as "log_type: LogType",
+                note
+            "#,
+            date,
+            location.id,
+            log_type,
+            note,
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(upserted)
+    }
+
+    pub async fn get_record_by_date(&self, date: Date) -> Result<Option<Record>> {
+        let row: Option<Record> = sqlx::query_as!(
+            Record,
+            r#"
+            SELECT
+                id as "id!",
+                date as "date: Date",
+                location_id as "location_id!",
+                log_type as "log_type: LogType",
+                note
+            FROM Record
+            WHERE date = ?
+            "#,
+            date,
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(row)
+    }
+
+    pub async fn delete_record_by_date(&self, date: Date) -> Result<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM Record
+            WHERE date = ?
+            "#,
+            date,
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// All records between `start` and `end`, both inclusive, ordered by date
+    pub async fn get_records_between(&self, start: Date, end: Date) -> Result<Vec<Record>> {
+        let rows: Vec<Record> = sqlx::query_as!(
+            Record,
+            r#"
+            SELECT
+                id as "id!",
+                date as "date: Date",
+                location_id as "location_id!",
+                log_type as "log_type: LogType",
+                note
+            FROM Record
+            WHERE date BETWEEN ? AND ?
+            ORDER BY date
+            "#,
+            start,
+            end,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows)
+    }
+
+    /// All records in the month containing `date`
+    pub async fn get_records_in_month(&self, date: Date) -> Result<Vec<Record>> {
+        let (first, last) = month_bounds(date);
+        self.get_records_between(first, last).await
+    }
+
+    /// All records logged at a location, ordered by date
+    pub async fn get_records_for_location(&self, location: &Location) -> Result<Vec<Record>> {
+        let rows: Vec<Record> = sqlx::query_as!(
+            Record,
+            r#"
+            SELECT
+                id as "id!",
+                date as "date: Date",
+                location_id as "location_id!",
+                log_type as "log_type: LogType",
+                note
+            FROM Record
+            WHERE location_id = ?
+            ORDER BY date
+            "#,
+            location.id,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows)
+    }
+
+    /// Records joined with their location, optionally filtered by an inclusive date range
+    /// and a location name
+    pub async fn get_record_details(
+        &self,
+        start: Option<Date>,
+        end: Option<Date>,
+        location: Option<&str>,
+    ) -> Result<Vec<RecordDetails>> {
+        let rows: Vec<RecordDetails> = sqlx::query_as!(
+            RecordDetails,
+            r#"
+            SELECT
+                r.id as "id!",
+                r.date as "date: Date",
+                r.log_type as "log_type: LogType",
+                r.note,
+                l.name as "location!",
+                l.tag as "tag!",
+                l.category as "category: Category"
+            FROM Record r
+            JOIN Location l ON l.id = r.location_id
+            WHERE (?1 IS NULL OR r.date >= ?1)
+                AND (?2 IS NULL OR r.date <= ?2)
+                AND (?3 IS NULL OR l.name = ?3)
+            ORDER BY r.date
+            "#,
+            start,
+            end,
+            location,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows)
+    }
+
+    /// Create the given locations and write the records in a single transaction.
+    ///
+    /// Every record must refer to an existing or newly created location, records on an
+    /// already logged date overwrite the existing record.
+    pub async fn import_records(
+        &self,
+        new_locations: &[String],
+        records: &[NamedRecord],
+    ) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        for name in new_locations {
+            sqlx::query!(
+                r#"
+                INSERT INTO Location (name, tag)
+                VALUES (?, '')
+                "#,
+                name
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        for record in records {
+            let location_id = sqlx::query_scalar!(
+                r#"
+                SELECT id AS "id!" FROM Location WHERE name = ?
+                "#,
+                record.location
+            )
+            .fetch_one(&mut *tx)
+            .await?;
+            sqlx::query!(
+                r#"
+                INSERT INTO Record (date, location_id, log_type)
+                VALUES (?, ?, ?)
+                ON CONFLICT(date) DO UPDATE SET
+                    location_id = excluded.location_id,
+                    log_type = excluded.log_type
+                "#,
+                record.date,
+                location_id,
+                record.log_type,
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Add a deleted location again together with its records, in a single transaction.
+    ///
+    /// Records on a day that has been logged since the deletion are skipped, returns the
+    /// restored location and the number of restored records.
+    pub async fn restore_location(
+        &self,
+        location: &Location,
+        records: &[Record],
+    ) -> Result<(Location, u64)> {
+        let mut tx = self.pool.begin().await?;
+        let restored: Location = sqlx::query_as!(
+            Location,
+            r#"
+                INSERT INTO Location (name, tag, archived, category)
+                VALUES (?, ?, ?, ?)
+                RETURNING id as "id!", name as "name!", tag as "tag!", archived as "archived: bool",
+                    category as "category: Category"
+            "#,
+            location.name,
+            location.tag,
+            location.archived,
+            location.category
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        let mut count = 0;
+        for record in records {
+            count += sqlx::query!(
+                r#"
+                INSERT INTO Record (date, location_id, log_type, note)
+                VALUES (?, ?, ?, ?)
+                ON CONFLICT(date) DO NOTHING
+                "#,
+                record.date,
+                restored.id,
+                record.log_type,
+                record.note,
+            )
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        }
+        tx.commit().await?;
+        Ok((restored, count))
+    }
 }
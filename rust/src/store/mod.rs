@@ -1,8 +1,13 @@
 pub mod connection;
 pub mod models;
+pub mod profile;
 
 pub use connection::Store;
 
+pub use models::Category;
 pub use models::Location;
 pub use models::LogType;
+pub use models::NamedRecord;
 pub use models::Record;
+pub use models::RecordDetails;
+pub use profile::Profile;
@@ -1,4 +1,4 @@
-use std::fmt::Display;
+use std::{fmt::Display, str::FromStr};
 
 use sqlx::prelude::FromRow;
 use time::Date;
@@ -8,6 +8,9 @@ pub struct Location {
     pub id: i64,
     pub name: String,
     pub tag: String,
+    /// Hidden from the pickers, its records still count in the stats
+    pub archived: bool,
+    pub category: Category,
 }
 
 #[derive(Clone, PartialEq, Eq, Debug, FromRow)]
@@ -16,6 +19,28 @@ pub struct Record {
     pub location_id: i64,
     pub date: Date,
     pub log_type: LogType,
+    /// Free text about the day
+    pub note: Option<String>,
+}
+
+/// A record joined with the name and tag of its location
+#[derive(Clone, PartialEq, Eq, Debug, FromRow)]
+pub struct RecordDetails {
+    pub id: i64,
+    pub date: Date,
+    pub log_type: LogType,
+    pub location: String,
+    pub tag: String,
+    pub category: Category,
+    pub note: Option<String>,
+}
+
+/// A record that refers to its location by name, e.g. when importing
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct NamedRecord {
+    pub location: String,
+    pub date: Date,
+    pub log_type: LogType,
 }
 
 #[derive(Clone, PartialEq, Eq, Debug, sqlx::Type)]
@@ -44,3 +69,66 @@ impl LogType {
         ]
     }
 }
+
+impl FromStr for LogType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "unknown" => Ok(LogType::Unknown),
+            "work" => Ok(LogType::Work),
+            "holiday" => Ok(LogType::Holiday),
+            "vacation" => Ok(LogType::Vacation),
+            "sick" => Ok(LogType::Sick),
+            _ => Err(format!("unknown log type '{s}'")),
+        }
+    }
+}
+
+/// What kind of place a location is, the hybrid-work stats are counted per category
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, sqlx::Type)]
+#[repr(i64)]
+pub enum Category {
+    Unknown = 0,
+    Home = 1,
+    Office = 2,
+    Client = 3,
+    Travel = 4,
+}
+
+impl Display for Category {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Category {
+    pub const ALL: [Category; 5] = [
+        Category::Unknown,
+        Category::Home,
+        Category::Office,
+        Category::Client,
+        Category::Travel,
+    ];
+
+    pub fn names() -> Vec<String> {
+        Category::ALL.iter().map(|c| c.to_string()).collect()
+    }
+}
+
+impl FromStr for Category {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "unknown" => Ok(Category::Unknown),
+            "home" => Ok(Category::Home),
+            "office" => Ok(Category::Office),
+            "client" => Ok(Category::Client),
+            "travel" => Ok(Category::Travel),
+            _ => Err(format!(
+                "unknown category '{s}', expected one of home, office, client, travel"
+            )),
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use crate::config::config_dir;
+use color_eyre::{Result, eyre::eyre};
+use std::{env, fs, path::PathBuf};
+
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// The database used by this session
+#[derive(Clone, Debug)]
+pub struct Profile {
+    /// Profile name, or the database path when it was given directly
+    pub name: String,
+    pub db_path: PathBuf,
+}
+
+fn profiles_dir() -> PathBuf {
+    config_dir().join("profiles")
+}
+
+/// Turn a `sqlite://path/to.db?mode=rwc` url into the path of the database
+fn path_from_url(url: &str) -> PathBuf {
+    let path = url
+        .strip_prefix("sqlite://")
+        .or(url.strip_prefix("sqlite:"))
+        .unwrap_or(url);
+    let path = path.split('?').next().unwrap_or(path);
+    PathBuf::from(path)
+}
+
+impl Profile {
+    /// A profile by name, `default` is the `app.db` in the config dir and every other
+    /// profile gets its own database in the `profiles` folder of the config dir
+    pub fn named(name: &str) -> Result<Self> {
+        if name == DEFAULT_PROFILE {
+            return Ok(Profile {
+                name: name.to_string(),
+                db_path: config_dir().join("app.db"),
+            });
+        }
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(eyre!(
+                "invalid profile name '{name}', use letters, digits, '-' and '_'"
+            ));
+        }
+        Ok(Profile {
+            name: name.to_string(),
+            db_path: profiles_dir().join(format!("{name}.db")),
+        })
+    }
+
+    /// Identifies the database in the UIDs of exported calendar events, so that exports of
+    /// several profiles or people can share a calendar. A hash of the database path that
+    /// stays the same across runs.
+    pub fn calendar_id(&self) -> String {
+        let path = fs::canonicalize(&self.db_path).unwrap_or(self.db_path.clone());
+        // FNV-1a, the std hasher is not guaranteed to stay the same between rust releases
+        let hash = path
+            .to_string_lossy()
+            .bytes()
+            .fold(0xcbf29ce484222325_u64, |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+            });
+        format!("{hash:016x}")
+    }
+
+    fn custom(db_path: PathBuf) -> Self {
+        Profile {
+            name: db_path.display().to_string(),
+            db_path,
+        }
+    }
+
+    /// Pick the database, in order of preference: the `--db` flag, the `--profile` flag,
+    /// the `LOCWORK_DB` and `DATABASE_URL` environment variables and the default profile
+    pub fn resolve(db: Option<PathBuf>, profile: Option<&str>) -> Result<Self> {
+        if let Some(path) = db {
+            return Ok(Profile::custom(path));
+        }
+        if let Some(name) = profile {
+            return Profile::named(name);
+        }
+        if let Ok(path) = env::var("LOCWORK_DB")
+            && !path.is_empty()
+        {
+            return Ok(Profile::custom(PathBuf::from(path)));
+        }
+        if let Ok(url) = env::var("DATABASE_URL")
+            && !url.is_empty()
+        {
+            return Ok(Profile::custom(path_from_url(&url)));
+        }
+        Profile::named(DEFAULT_PROFILE)
+    }
+
+    /// Names of the default profile and every profile that has a database
+    pub fn list() -> Vec<String> {
+        let mut names = vec![DEFAULT_PROFILE.to_string()];
+        if let Ok(entries) = fs::read_dir(profiles_dir()) {
+            let mut profiles: Vec<String> = entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "db"))
+                .filter_map(|path| Some(path.file_stem()?.to_string_lossy().to_string()))
+                .collect();
+            profiles.sort();
+            names.extend(profiles);
+        }
+        names
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calendar_id_is_stable_per_database() {
+        let work = Profile::custom(PathBuf::from("/tmp/locwork-work.db"));
+        let other = Profile::custom(PathBuf::from("/tmp/locwork-other.db"));
+        assert_eq!(work.calendar_id(), work.clone().calendar_id());
+        assert_eq!(work.calendar_id().len(), 16);
+        assert_ne!(work.calendar_id(), other.calendar_id());
+    }
+}